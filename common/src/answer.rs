@@ -0,0 +1,109 @@
+use std::fmt::{self, Display};
+
+use serde::{Serialize, Serializer};
+
+/// What a part of a day came to. Integers compare by value whichever width
+/// they were returned in, so `Integer(5) == BigInteger(5)`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Integer(u64),
+    BigInteger(u128),
+    Text(String),
+    /// The part has not been solved yet.
+    Unsolved,
+}
+
+impl Answer {
+    pub fn is_solved(&self) -> bool {
+        !matches!(self, Answer::Unsolved)
+    }
+
+    /// The answer as a number, if it is one.
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Answer::Integer(number) => Some(*number as u128),
+            Answer::BigInteger(number) => Some(*number),
+            Answer::Text(_) | Answer::Unsolved => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Text(left), Answer::Text(right)) => left == right,
+            (Answer::Unsolved, Answer::Unsolved) => true,
+            _ => self.as_u128().is_some() && self.as_u128() == other.as_u128(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(number) => write!(f, "{number}"),
+            Answer::BigInteger(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Unsolved => write!(f, "not implemented"),
+        }
+    }
+}
+
+/// Integers serialise as numbers, text as a string and an unsolved part as
+/// a null.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(number) => serializer.serialize_u64(*number),
+            Answer::BigInteger(number) => serializer.serialize_u128(*number),
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Unsolved => serializer.serialize_none(),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(number: u32) -> Answer {
+        Answer::Integer(number.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(number: u64) -> Answer {
+        Answer::Integer(number)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(number: u128) -> Answer {
+        Answer::BigInteger(number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_compares_integers_by_value() {
+        assert_eq!(Answer::Integer(54927), Answer::BigInteger(54927));
+        assert_ne!(Answer::Integer(0), Answer::Unsolved);
+        assert_ne!(Answer::Integer(1), Answer::Text("1".to_string()));
+        assert_eq!(Answer::Unsolved, Answer::Unsolved);
+    }
+
+    #[test]
+    fn it_serialises_to_json_values() {
+        let answers = [Answer::Integer(15517), Answer::BigInteger(1 << 100), Answer::Text("AAA".to_string()), Answer::Unsolved];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"[15517,1267650600228229401496703205376,"AAA",null]"#);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{any::Any, fmt::{self, Debug, Display}, ops::Range, panic::{self, AssertUnwindSafe}};
+
+/// A solver's answer, or the message it panicked with.
+pub type Outcome<T> = Result<T, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<T> {
+    pub size: usize,
+    pub seed: u64,
+    /// The smallest set of lines from the generated input that still
+    /// produces different answers.
+    pub input: String,
+    pub reference: Outcome<T>,
+    pub optimised: Outcome<T>,
+}
+
+impl<T: Debug> Display for Disagreement<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "size {}, seed {}: reference gave {:?} but optimised gave {:?} for", self.size, self.seed, self.reference, self.optimised)?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Runs a slow reference solver and an optimised one over generated inputs
+/// of every size and seed, returning the first disagreement with its input
+/// minimised. A solver that panics disagrees with one that does not.
+pub fn find_disagreement<T, G, R, O>(
+    generate: G,
+    reference: R,
+    optimised: O,
+    sizes: &[usize],
+    seeds: Range<u64>,
+) -> Option<Disagreement<T>>
+where
+    T: PartialEq,
+    G: Fn(usize, u64) -> String,
+    R: Fn(&str) -> T,
+    O: Fn(&str) -> T,
+{
+    for size in sizes {
+        for seed in seeds.clone() {
+            let input = generate(*size, seed);
+            if compare(&input, &reference, &optimised).is_some() {
+                let input = minimise(&input, &reference, &optimised);
+                return Some(Disagreement {
+                    size: *size,
+                    seed,
+                    reference: run(&reference, &input),
+                    optimised: run(&optimised, &input),
+                    input,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Removes lines from `input` for as long as the two solvers still disagree,
+/// using delta debugging. Only candidates where the same solvers panic as
+/// on `input` reproduce the disagreement, so a wrong answer is not traded
+/// for a panic on malformed input. Panic messages are still printed, as the
+/// panic hook is shared with the rest of the process.
+pub fn minimise<T, R, O>(input: &str, reference: R, optimised: O) -> String
+where
+    T: PartialEq,
+    R: Fn(&str) -> T,
+    O: Fn(&str) -> T,
+{
+    let original = compare(input, &reference, &optimised);
+    let disagrees = |lines: &[&str]| {
+        original.is_some() && compare(&join_lines(lines), &reference, &optimised) == original
+    };
+
+    let mut lines: Vec<_> = input.lines().collect();
+    let mut chunks = 2;
+    while lines.len() >= 2 {
+        let chunk_size = lines.len().div_ceil(chunks);
+        let reduced = (0..lines.len()).step_by(chunk_size).find_map(|start| {
+            let mut candidate = lines[..start].to_vec();
+            candidate.extend_from_slice(&lines[(start + chunk_size).min(lines.len())..]);
+            disagrees(&candidate).then_some(candidate)
+        });
+        match reduced {
+            Some(candidate) => {
+                lines = candidate;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunk_size == 1 => break,
+            None => chunks = (chunks * 2).min(lines.len()),
+        }
+    }
+    join_lines(&lines)
+}
+
+/// Whether the solvers disagree on `input` and, if so, whether each of
+/// them panicked.
+fn compare<T: PartialEq>(input: &str, reference: impl Fn(&str) -> T, optimised: impl Fn(&str) -> T) -> Option<(bool, bool)> {
+    let reference = run(reference, input);
+    let optimised = run(optimised, input);
+    (reference != optimised).then_some((reference.is_err(), optimised.is_err()))
+}
+
+fn run<T>(solver: impl Fn(&str) -> T, input: &str) -> Outcome<T> {
+    panic::catch_unwind(AssertUnwindSafe(|| solver(input))).map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// The message a panic was raised with, for payloads from `panic!`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload.downcast_ref::<&str>().map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".to_string())
+}
+
+fn join_lines(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(size: usize, seed: u64) -> String {
+        (0..size as u64).map(|line| format!("{}\n", line * seed)).collect()
+    }
+
+    fn sum(input: &str) -> u64 {
+        input.lines().map(|line| line.parse::<u64>().unwrap()).sum()
+    }
+
+    fn sum_ignoring_sevens(input: &str) -> u64 {
+        input.lines().map(|line| line.parse::<u64>().unwrap()).filter(|number| number % 7 != 0 || *number == 0).sum()
+    }
+
+    #[test]
+    fn it_finds_no_disagreement_between_equal_solvers() {
+        assert_eq!(find_disagreement(generate, sum, sum, &[1, 10], 0..5), None);
+    }
+
+    #[test]
+    fn it_reports_a_minimised_disagreement() {
+        let result = find_disagreement(generate, sum, sum_ignoring_sevens, &[10], 0..5).unwrap();
+        assert_eq!(result.seed, 1);
+        assert_eq!(result.input, "7\n");
+        assert_eq!((result.reference, result.optimised), (Ok(7), Ok(0)));
+    }
+
+    #[test]
+    fn it_reports_a_solver_that_panics() {
+        let sum_rejecting_sevens = |input: &str| {
+            assert!(!input.lines().any(|line| line == "7"), "found a seven");
+            sum(input)
+        };
+        let result = find_disagreement(generate, sum, sum_rejecting_sevens, &[10], 0..5).unwrap();
+        assert_eq!(result.input, "7\n");
+        assert_eq!((result.reference, result.optimised), (Ok(7), Err("found a seven".to_string())));
+    }
+}
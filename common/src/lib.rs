@@ -1,3 +1,18 @@
+mod answer;
+mod differential;
+mod normalise;
+mod parse_error;
+pub mod parser;
+mod rng;
+mod sections;
 mod solution;
+mod word_scanner;
 
-pub use solution::Solution;
\ No newline at end of file
+pub use answer::Answer;
+pub use differential::{find_disagreement, minimise, panic_message, Disagreement, Outcome};
+pub use normalise::{normalise, InputLines};
+pub use parse_error::{column_of, ensure_ascii, ParseError};
+pub use rng::Rng;
+pub use sections::{Section, Sections};
+pub use solution::{Solution, StreamingSolution};
+pub use word_scanner::{WordMatch, WordScanner};
@@ -0,0 +1,109 @@
+use std::{borrow::Cow, io::{self, BufRead}};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Puzzle input as the days expect it: no byte order mark, `\n` line
+/// endings, no trailing whitespace (any Unicode whitespace, not only spaces
+/// and tabs) and no blank lines at the end, with the last line ending in
+/// `\n`. Lines keep their numbers and characters keep their columns, so
+/// errors still point at the original file. Input that is already in this
+/// form is borrowed rather than copied.
+pub fn normalise(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+    let content = input.trim_end();
+    if content.is_empty() {
+        return Cow::Borrowed("");
+    }
+    let is_normal = input.len() == content.len() + 1
+        && input.ends_with('\n')
+        && !input.contains('\r')
+        && content.lines().all(|line| line.len() == line.trim_end().len());
+    if is_normal {
+        return Cow::Borrowed(input);
+    }
+    let mut result = String::with_capacity(content.len() + 1);
+    for line in content.lines() {
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+    Cow::Owned(result)
+}
+
+/// The lines of some puzzle input read one at a time, as `normalise` would
+/// leave them. Blank lines are held back until a later line shows they are
+/// not at the end, so only the current line and a count are kept in memory.
+pub struct InputLines<R> {
+    reader: R,
+    at_start: bool,
+    blank_lines: usize,
+    waiting: Option<String>,
+}
+
+impl<R: BufRead> InputLines<R> {
+    pub fn new(reader: R) -> InputLines<R> {
+        InputLines { reader, at_start: true, blank_lines: 0, waiting: None }
+    }
+}
+
+impl<R: BufRead> Iterator for InputLines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        if let Some(line) = self.waiting.take() {
+            if self.blank_lines == 0 {
+                return Some(Ok(line));
+            }
+            self.blank_lines -= 1;
+            self.waiting = Some(line);
+            return Some(Ok(String::new()));
+        }
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => return Some(Err(error)),
+            }
+            if self.at_start && line.starts_with(BYTE_ORDER_MARK) {
+                line.drain(..BYTE_ORDER_MARK.len_utf8());
+            }
+            self.at_start = false;
+            line.truncate(line.trim_end().len());
+            if line.is_empty() {
+                self.blank_lines += 1;
+            } else {
+                self.waiting = Some(line);
+                return self.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_strips_byte_order_marks_carriage_returns_and_trailing_whitespace() {
+        let input = "\u{feff}Time: 7 15 \r\n\r\nDistance:\t9 40\t\r\n \r\n\r\n";
+        assert_eq!(normalise(input), "Time: 7 15\n\nDistance:\t9 40\n");
+        assert_eq!(normalise("a\u{a0}\u{3000}\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn it_borrows_input_that_is_already_normal() {
+        assert!(matches!(normalise("a\nb\n"), Cow::Borrowed("a\nb\n")));
+        assert!(matches!(normalise("\u{feff}a\n"), Cow::Borrowed("a\n")));
+        assert_eq!(normalise("a\nb"), "a\nb\n");
+        assert_eq!(normalise("a\r\nb\n"), "a\nb\n");
+        assert_eq!(normalise(" \n\n"), "");
+    }
+
+    #[test]
+    fn it_reads_the_same_lines_as_normalise_leaves() {
+        for input in ["\u{feff}a \r\n\r\n\tb\r\n \r\n\r\n", "\n\na\n\n\nb", "", "\u{feff}\r\n"] {
+            let lines: Vec<String> = InputLines::new(input.as_bytes()).map(Result::unwrap).collect();
+            assert_eq!(lines, normalise(input).lines().collect::<Vec<_>>(), "{input:?}");
+        }
+    }
+}
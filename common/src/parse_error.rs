@@ -0,0 +1,86 @@
+use std::{error::Error, fmt::{self, Display}};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError { line, column, message: message.into() }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Rejects any non-ASCII character so that byte offsets can be used as
+/// columns. Lines and columns are reported 1-based.
+pub fn ensure_ascii(input: &str) -> Result<(), ParseError> {
+    for (line_index, line) in input.lines().enumerate() {
+        if let Some((column, character)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(ParseError::new(
+                line_index + 1,
+                column + 1,
+                format!("unexpected non-ASCII character {character:?}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The 1-based column of `part`, ignoring any whitespace at the start of
+/// `part`. If `part` is not a slice of `line` there is no column to give,
+/// so the error is put at column 1.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let Some(offset) = offset_in(line, part) else {
+        return 1;
+    };
+    let leading_whitespace = part.len() - part.trim_start().len();
+    line[..offset + leading_whitespace].chars().count() + 1
+}
+
+/// The byte offset of `part` in `source`, if `part` is a slice of it.
+pub(crate) fn offset_in(source: &str, part: &str) -> Option<usize> {
+    let offset = (part.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+    let within = offset.checked_add(part.len()).is_some_and(|end| end <= source.len());
+    (within && source.is_char_boundary(offset)).then_some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_column_of_a_slice() {
+        let line = "Game 1: 3 blue";
+        assert_eq!(column_of(line, &line[7..]), 9);
+    }
+
+    #[test]
+    fn it_puts_slices_of_other_strings_at_column_one() {
+        let line = "Game 1: 3 blue";
+        assert_eq!(column_of(&line[5..], line), 1);
+        assert_eq!(column_of(&line[..5], &line[7..]), 1);
+        let copy = String::from("3 blue");
+        assert_eq!(column_of(line, &copy), 1);
+    }
+
+    #[test]
+    fn it_accepts_ascii() {
+        assert_eq!(ensure_ascii("abc\n123"), Ok(()));
+    }
+
+    #[test]
+    fn it_reports_position_of_non_ascii() {
+        let result = ensure_ascii("abc\n1é3");
+        assert_eq!(result, Err(ParseError::new(2, 2, "unexpected non-ASCII character 'é'")));
+    }
+}
@@ -0,0 +1,342 @@
+use std::str::FromStr;
+
+use crate::{parse_error::offset_in, ParseError};
+
+/// The unparsed part of some source text. Parsers take an `Input` and
+/// return what they read along with the `Input` that follows it, borrowing
+/// from the source throughout. Positions are only worked out when an error
+/// is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    source: &'a str,
+    pub(crate) offset: usize,
+    pub(crate) end: usize,
+    first_line: usize,
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Input<'a> {
+        Input::at_line(source, 1)
+    }
+
+    /// Input starting on the given 1-based line, for text that has already
+    /// been split out of a larger source.
+    pub fn at_line(source: &'a str, line_number: usize) -> Input<'a> {
+        Input { source, offset: 0, end: source.len(), first_line: line_number }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offset == self.end
+    }
+
+    /// The 1-based line and column at the start of this input.
+    pub fn position(&self) -> (usize, usize) {
+        let before = &self.source[..self.offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let line = self.first_line + before.matches('\n').count();
+        (line, before[line_start..].chars().count() + 1)
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        let (line, column) = self.position();
+        ParseError::new(line, column, message)
+    }
+
+    /// An error at the start of `part`, a slice of this input's source such
+    /// as one returned by a parser. Any other `part` has no position in the
+    /// source, so the error is put at the start of this input instead.
+    pub fn error_at(&self, part: &str, message: impl Into<String>) -> ParseError {
+        match offset_in(self.source, part) {
+            Some(offset) => self.at(offset).error(message),
+            None => self.error(message),
+        }
+    }
+
+    /// Each line of the input, without its line ending.
+    pub fn lines(self) -> impl Iterator<Item = Input<'a>> {
+        let mut rest = Some(self);
+        std::iter::from_fn(move || {
+            let input = rest.filter(|input| !input.is_empty())?;
+            let (length, next) = match input.rest().find('\n') {
+                Some(index) => (index, Some(input.advance(index + 1))),
+                None => (input.rest().len(), None),
+            };
+            rest = next;
+            let line = input.rest()[..length].strip_suffix('\r').unwrap_or(&input.rest()[..length]);
+            Some(input.until(input.offset + line.len()))
+        })
+    }
+
+    /// Runs of non-blank lines separated by one or more blank lines.
+    pub fn blocks(self) -> impl Iterator<Item = Input<'a>> {
+        let mut lines = self.lines().peekable();
+        std::iter::from_fn(move || {
+            let first = loop {
+                let line = lines.next()?;
+                if !line.rest().trim().is_empty() {
+                    break line;
+                }
+            };
+            let mut last = first;
+            while let Some(line) = lines.next_if(|line| !line.rest().trim().is_empty()) {
+                last = line;
+            }
+            Some(first.until(last.end))
+        })
+    }
+
+    pub(crate) fn at(&self, offset: usize) -> Input<'a> {
+        Input { offset, ..*self }
+    }
+
+    fn advance(&self, bytes: usize) -> Input<'a> {
+        self.at(self.offset + bytes)
+    }
+
+    pub(crate) fn until(&self, end: usize) -> Input<'a> {
+        Input { end, ..*self }
+    }
+
+    fn split(&self, bytes: usize) -> (&'a str, Input<'a>) {
+        (&self.rest()[..bytes], self.advance(bytes))
+    }
+}
+
+/// Matches `expected` exactly.
+pub fn tag<'a>(expected: &'static str) -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input| {
+        if input.rest().starts_with(expected) {
+            Ok(input.split(expected.len()))
+        } else {
+            Err(input.error(format!("expected '{expected}'")))
+        }
+    }
+}
+
+/// Any number of spaces and tabs, which never fails.
+pub fn spaces<'a>(input: Input<'a>) -> ParseResult<'a, &'a str> {
+    let length = input.rest().len() - input.rest().trim_start_matches([' ', '\t']).len();
+    Ok(input.split(length))
+}
+
+/// A name made of letters, digits and underscores.
+pub fn word<'a>(input: Input<'a>) -> ParseResult<'a, &'a str> {
+    let length = input.rest().len() - input.rest().trim_start_matches(|c: char| c.is_alphanumeric() || c == '_').len();
+    if length == 0 {
+        return Err(input.error(format!("expected a name, found {:?}", found(input))));
+    }
+    Ok(input.split(length))
+}
+
+/// Everything up to the next whitespace or punctuation other than a sign,
+/// such as a number with anything that is stuck to it.
+pub fn token<'a>(input: Input<'a>) -> ParseResult<'a, &'a str> {
+    let text = next_token(input);
+    if text.is_empty() {
+        return Err(input.error("expected a value"));
+    }
+    Ok(input.split(text.len()))
+}
+
+/// A token parsed as a number of type `T`.
+pub fn integer<T: FromStr>(input: Input<'_>) -> ParseResult<'_, T> {
+    let (text, rest) = token(input).map_err(|_| input.error("expected a number"))?;
+    match text.parse::<T>() {
+        Ok(number) => Ok((number, rest)),
+        Err(_) => Err(input.error(format!("invalid number {text:?}"))),
+    }
+}
+
+/// Spaces, then the end of a line or of the input.
+pub fn end_of_line(input: Input<'_>) -> ParseResult<'_, ()> {
+    let (_, input) = spaces(input)?;
+    for ending in ["\r\n", "\n"] {
+        if input.rest().starts_with(ending) {
+            return Ok(((), input.advance(ending.len())));
+        }
+    }
+    if input.is_empty() {
+        return Ok(((), input));
+    }
+    Err(input.error(format!("unexpected {:?}", found(input))))
+}
+
+/// Succeeds only if nothing but whitespace is left.
+pub fn end(input: Input<'_>) -> ParseResult<'_, ()> {
+    let remaining = input.rest().trim_start();
+    if remaining.is_empty() {
+        return Ok(((), input.advance(input.rest().len())));
+    }
+    let input = input.advance(input.rest().len() - remaining.len());
+    Err(input.error(format!("unexpected {:?}", found(input))))
+}
+
+/// `parser`, then the end of the line.
+pub fn line<'a, T>(parser: impl Fn(Input<'a>) -> ParseResult<'a, T>) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input| {
+        let (value, input) = parser(input)?;
+        let (_, input) = end_of_line(input)?;
+        Ok((value, input))
+    }
+}
+
+/// `label` and any spaces, then `parser`, as in `seeds: 79 14 55 13`.
+pub fn labelled<'a, T>(
+    label: &'static str,
+    parser: impl Fn(Input<'a>) -> ParseResult<'a, T>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    let label = tag(label);
+    move |input| {
+        let (_, input) = label(input)?;
+        let (_, input) = spaces(input)?;
+        parser(input)
+    }
+}
+
+/// `parser` between `open` and `close`, as in `(BBB, CCC)`.
+pub fn delimited<'a, T>(
+    open: &'static str,
+    parser: impl Fn(Input<'a>) -> ParseResult<'a, T>,
+    close: &'static str,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    let (open, close) = (tag(open), tag(close));
+    move |input| {
+        let (_, input) = open(input)?;
+        let (value, input) = parser(input)?;
+        let (_, input) = close(input)?;
+        Ok((value, input))
+    }
+}
+
+/// One or more items separated by `separator` and any spaces around it.
+pub fn separated<'a, T>(
+    parser: impl Fn(Input<'a>) -> ParseResult<'a, T>,
+    separator: &'static str,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    let separator = tag(separator);
+    move |input| {
+        let (first, mut input) = parser(input)?;
+        let mut result = vec![first];
+        loop {
+            let (_, after_spaces) = spaces(input)?;
+            let Ok((_, after_separator)) = separator(after_spaces) else {
+                return Ok((result, input));
+            };
+            let (_, item_start) = spaces(after_separator)?;
+            let (item, rest) = parser(item_start)?;
+            result.push(item);
+            input = rest;
+        }
+    }
+}
+
+/// Space separated items up to the end of the line or the next
+/// punctuation, as in the `41 48 83` of `Card 1: 41 48 83 | 83 86`. The list
+/// may be empty.
+pub fn list<'a, T>(parser: impl Fn(Input<'a>) -> ParseResult<'a, T>) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |input| {
+        let mut result = Vec::new();
+        let (_, mut input) = spaces(input)?;
+        while !next_token(input).is_empty() {
+            let (item, rest) = parser(input)?;
+            result.push(item);
+            (_, input) = spaces(rest)?;
+        }
+        Ok((result, input))
+    }
+}
+
+/// `parser`, with its result checked or converted by `convert`. A failed
+/// conversion is reported at the start of what `parser` read.
+pub fn try_map<'a, T, U>(
+    parser: impl Fn(Input<'a>) -> ParseResult<'a, T>,
+    convert: impl Fn(T) -> Result<U, String>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, U> {
+    move |input| {
+        let (value, rest) = parser(input)?;
+        let value = convert(value).map_err(|message| input.error(message))?;
+        Ok((value, rest))
+    }
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || (c.is_ascii_punctuation() && c != '-' && c != '+')
+}
+
+/// The token at the start of `input`, or its first character if that is
+/// punctuation, for error messages.
+fn found<'a>(input: Input<'a>) -> &'a str {
+    match next_token(input) {
+        "" => input.rest().chars().next().map_or("", |c| &input.rest()[..c.len_utf8()]),
+        token => token,
+    }
+}
+
+fn next_token<'a>(input: Input<'a>) -> &'a str {
+    let rest = input.rest();
+    let length = rest.find(is_delimiter).unwrap_or(rest.len());
+    &rest[..length]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_labelled_lists() {
+        let input = Input::new("Time:  7 15\nDistance: 9 40\n");
+        let (times, input) = line(labelled("Time:", list(integer::<u64>)))(input).unwrap();
+        let (distances, input) = line(labelled("Distance:", list(integer::<u64>)))(input).unwrap();
+        assert_eq!((times, distances), (vec![7, 15], vec![9, 40]));
+        assert!(end(input).is_ok());
+    }
+
+    #[test]
+    fn it_stops_lists_at_punctuation() {
+        let input = Input::new("41 48 | 83");
+        let (winning, input) = list(integer::<u8>)(input).unwrap();
+        assert_eq!(winning, vec![41, 48]);
+        assert_eq!(input.rest(), "| 83");
+    }
+
+    #[test]
+    fn it_parses_delimited_and_separated_items() {
+        let input = Input::new("(BBB, CCC)");
+        let (names, _) = delimited("(", separated(word, ","), ")")(input).unwrap();
+        assert_eq!(names, vec!["BBB", "CCC"]);
+    }
+
+    #[test]
+    fn it_reports_the_position_of_failures() {
+        let input = Input::new("seeds: 1 2\nseeds: 3 x4");
+        let (_, input) = line(labelled("seeds:", list(integer::<u64>)))(input).unwrap();
+        let result = line(labelled("seeds:", list(integer::<u64>)))(input);
+        assert_eq!(result, Err(ParseError::new(2, 10, "invalid number \"x4\"")));
+        let result = tag("Card")(Input::at_line("Game 1", 7));
+        assert_eq!(result, Err(ParseError::new(7, 1, "expected 'Card'")));
+    }
+
+    #[test]
+    fn it_reports_errors_in_other_strings_at_the_start_of_the_input() {
+        let source = "seeds: 1 2\nseeds: 3 x4";
+        let (_, input) = line(labelled("seeds:", list(integer::<u64>)))(Input::new(source)).unwrap();
+        assert_eq!(input.error_at(&source[18..], "here"), ParseError::new(2, 8, "here"));
+        let other = String::from("x4");
+        assert_eq!(input.error_at(&other, "elsewhere"), ParseError::new(2, 1, "elsewhere"));
+    }
+
+    #[test]
+    fn it_splits_blank_line_separated_blocks() {
+        let input = Input::new("a\r\nb\r\n\r\n\r\nc\n\n");
+        let blocks: Vec<_> = input.blocks().map(|block| block.rest()).collect();
+        assert_eq!(blocks, vec!["a\r\nb", "c"]);
+        let block = input.blocks().nth(1).unwrap();
+        assert_eq!(block.position(), (5, 1));
+    }
+}
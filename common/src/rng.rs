@@ -0,0 +1,72 @@
+/// A small seeded SplitMix64 generator. Its output for a given seed never
+/// changes, so generated inputs can be reproduced from their seed alone.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`. `bound` must not be zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// A number in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.below(span),
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            let other = self.below(index as u64 + 1) as usize;
+            items.swap(index, other);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_reproducible_from_its_seed() {
+        let first: Vec<_> = (0..5).map({ let mut rng = Rng::new(7); move |_| rng.next_u64() }).collect();
+        let second: Vec<_> = (0..5).map({ let mut rng = Rng::new(7); move |_| rng.next_u64() }).collect();
+        assert_eq!(first, second);
+        assert_ne!(Rng::new(8).next_u64(), first[0]);
+    }
+
+    #[test]
+    fn it_stays_within_bounds() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let value = rng.between(3, 9);
+            assert!((3..=9).contains(&value));
+        }
+        assert_eq!(rng.between(5, 5), 5);
+    }
+}
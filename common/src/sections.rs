@@ -0,0 +1,86 @@
+use crate::{parser::Input, ParseError};
+
+/// A block of lines whose first line may name it, as `seed-to-soil map:`
+/// names the ranges below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The first line without its trailing `:`, if it ends in one.
+    pub name: Option<Input<'a>>,
+    /// The lines after the name, or every line if there is no name.
+    pub body: Input<'a>,
+    /// The whole block, for reporting errors about the section as a whole.
+    pub block: Input<'a>,
+}
+
+impl<'a> Section<'a> {
+    pub fn new(block: Input<'a>) -> Section<'a> {
+        let first_line = block.lines().next().unwrap_or(block);
+        let name = first_line.rest().trim_end().strip_suffix(':')
+            .map(|name| first_line.until(first_line.offset + name.len()));
+        let body = match (name, block.lines().nth(1)) {
+            (None, _) => block,
+            (Some(_), Some(second_line)) => second_line.until(block.end),
+            (Some(_), None) => block.at(block.end),
+        };
+        Section { name, body, block }
+    }
+}
+
+/// Input split at blank lines into the block at the top and the sections
+/// that follow it. Lines holding only whitespace count as blank, and either
+/// line ending is accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections<'a> {
+    pub header: Input<'a>,
+    pub sections: Vec<Section<'a>>,
+}
+
+impl<'a> Sections<'a> {
+    pub fn split(input: &'a str) -> Result<Sections<'a>, ParseError> {
+        let input = Input::new(input);
+        let mut blocks = input.blocks();
+        let Some(header) = blocks.next() else {
+            return Err(input.error("expected some input"));
+        };
+        Ok(Sections { header, sections: blocks.map(Section::new).collect() })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Section<'a>> {
+        self.sections.iter().find(|section| section.name.is_some_and(|found| found.rest() == name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_a_header_from_named_sections() {
+        let sections = Sections::split("seeds: 1 2\r\n\r\na-to-b map: \r\n1 2 3\r\n4 5 6\r\n\r\nc-to-d map:\n7 8 9\n  \n\n").unwrap();
+        assert_eq!(sections.header.rest(), "seeds: 1 2");
+        assert_eq!(sections.sections.len(), 2);
+        let section = sections.get("a-to-b map").unwrap();
+        assert_eq!(section.body.rest(), "1 2 3\r\n4 5 6");
+        assert_eq!(section.body.position(), (4, 1));
+        assert_eq!(sections.get("c-to-d map").unwrap().body.rest(), "7 8 9");
+    }
+
+    #[test]
+    fn it_keeps_unnamed_sections_whole() {
+        let sections = Sections::split("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)").unwrap();
+        assert_eq!(sections.sections[0].name, None);
+        assert_eq!(sections.sections[0].body.rest(), "AAA = (BBB, CCC)\nBBB = (AAA, AAA)");
+    }
+
+    #[test]
+    fn it_allows_named_sections_without_a_body() {
+        let section = Section::new(Input::new("empty:"));
+        assert_eq!(section.name.map(|name| name.rest()), Some("empty"));
+        assert!(section.body.is_empty());
+    }
+
+    #[test]
+    fn it_needs_a_header() {
+        assert_eq!(Sections::split(" \n\n"), Err(ParseError::new(1, 1, "expected some input")));
+    }
+}
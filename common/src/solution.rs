@@ -1,6 +1,21 @@
-use std::fmt::Display;
+use std::{error::Error, io::BufRead};
+
+use crate::Answer;
 
 pub trait Solution {
-    fn part_one(&self, input: &str) -> Box<dyn Display>;
-    fn part_two(&self, input: &str) -> Box<dyn Display>;
-}
\ No newline at end of file
+    fn part_one(&self, input: &str) -> Result<Answer, Box<dyn Error>>;
+    fn part_two(&self, input: &str) -> Result<Answer, Box<dyn Error>>;
+
+    /// The same parts read a line at a time, for days that never need the
+    /// whole input at once.
+    fn streaming(&self) -> Option<&dyn StreamingSolution> {
+        None
+    }
+}
+
+/// Both parts of a day solved from a reader, in memory that does not grow
+/// with the length of the input.
+pub trait StreamingSolution {
+    fn part_one(&self, input: &mut dyn BufRead) -> Result<Answer, Box<dyn Error>>;
+    fn part_two(&self, input: &mut dyn BufRead) -> Result<Answer, Box<dyn Error>>;
+}
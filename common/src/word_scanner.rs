@@ -0,0 +1,227 @@
+use std::collections::VecDeque;
+
+const ALPHABET: usize = 256;
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+struct Automaton {
+    transitions: Vec<usize>,
+    outputs: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    fn build<'a>(patterns: impl Iterator<Item = &'a [u8]>) -> Automaton {
+        let mut transitions = vec![usize::MAX; ALPHABET];
+        let mut outputs = vec![Vec::new()];
+        for (pattern_index, pattern) in patterns.enumerate() {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = ROOT;
+            for byte in pattern {
+                let next = transitions[state * ALPHABET + *byte as usize];
+                state = if next == usize::MAX {
+                    let new_state = outputs.len();
+                    transitions[state * ALPHABET + *byte as usize] = new_state;
+                    transitions.extend([usize::MAX; ALPHABET]);
+                    outputs.push(Vec::new());
+                    new_state
+                } else {
+                    next
+                };
+            }
+            outputs[state].push(pattern_index);
+        }
+
+        let mut failures = vec![ROOT; outputs.len()];
+        let mut queue = VecDeque::new();
+        for byte in 0..ALPHABET {
+            let next = transitions[ROOT * ALPHABET + byte];
+            if next == usize::MAX {
+                transitions[ROOT * ALPHABET + byte] = ROOT;
+            } else {
+                queue.push_back(next);
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let failure_outputs = outputs[failures[state]].clone();
+            outputs[state].extend(failure_outputs);
+            for byte in 0..ALPHABET {
+                let next = transitions[state * ALPHABET + byte];
+                let fallback = transitions[failures[state] * ALPHABET + byte];
+                if next == usize::MAX {
+                    transitions[state * ALPHABET + byte] = fallback;
+                } else {
+                    failures[next] = fallback;
+                    queue.push_back(next);
+                }
+            }
+        }
+        Automaton { transitions, outputs }
+    }
+
+    fn step(&self, state: usize, byte: u8) -> usize {
+        self.transitions[state * ALPHABET + byte as usize]
+    }
+}
+
+/// Finds occurrences of a fixed set of words in a single pass using an
+/// Aho-Corasick automaton. Matching is done on bytes, so offsets in the
+/// returned matches are byte offsets. Empty patterns never match.
+pub struct WordScanner {
+    forward: Automaton,
+    backward: Automaton,
+    lengths: Vec<usize>,
+    max_length: usize,
+}
+
+impl WordScanner {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> WordScanner {
+        let forward = Automaton::build(patterns.iter().map(|pattern| pattern.as_ref().as_bytes()));
+        let reversed: Vec<Vec<u8>> = patterns.iter()
+            .map(|pattern| pattern.as_ref().bytes().rev().collect())
+            .collect();
+        let backward = Automaton::build(reversed.iter().map(Vec::as_slice));
+        let lengths: Vec<_> = patterns.iter().map(|pattern| pattern.as_ref().len()).collect();
+        let max_length = lengths.iter().copied().max().unwrap_or(0);
+        WordScanner { forward, backward, lengths, max_length }
+    }
+
+    /// Every match in the haystack, including overlapping ones, ordered by
+    /// end offset and then by start offset.
+    pub fn find_overlapping(&self, haystack: &str) -> Vec<WordMatch> {
+        let mut result = Vec::new();
+        let mut state = ROOT;
+        for (index, byte) in haystack.bytes().enumerate() {
+            state = self.forward.step(state, byte);
+            let mut matches: Vec<_> = self.forward.outputs[state].iter()
+                .map(|pattern| self.make_match(*pattern, index + 1))
+                .collect();
+            matches.sort_by_key(|word_match| word_match.start);
+            result.extend(matches);
+        }
+        result
+    }
+
+    /// The match that starts earliest in the haystack.
+    pub fn first_match(&self, haystack: &str) -> Option<WordMatch> {
+        let mut best: Option<WordMatch> = None;
+        let mut state = ROOT;
+        for (index, byte) in haystack.bytes().enumerate() {
+            if let Some(found) = best {
+                // A match starting before `found` must end within max_length of its start.
+                if index >= found.start + self.max_length {
+                    break;
+                }
+            }
+            state = self.forward.step(state, byte);
+            for pattern in &self.forward.outputs[state] {
+                let candidate = self.make_match(*pattern, index + 1);
+                if best.is_none_or(|found| candidate.start < found.start) {
+                    best = Some(candidate);
+                }
+            }
+        }
+        best
+    }
+
+    /// The match that starts latest in the haystack, found by scanning
+    /// backwards from its end.
+    pub fn last_match(&self, haystack: &str) -> Option<WordMatch> {
+        let bytes = haystack.as_bytes();
+        let mut state = ROOT;
+        for (index, byte) in bytes.iter().enumerate().rev() {
+            state = self.backward.step(state, *byte);
+            let longest = self.backward.outputs[state].iter()
+                .max_by_key(|pattern| self.lengths[**pattern]);
+            if let Some(pattern) = longest {
+                return Some(WordMatch { pattern: *pattern, start: index, end: index + self.lengths[*pattern] });
+            }
+        }
+        None
+    }
+
+    fn make_match(&self, pattern: usize, end: usize) -> WordMatch {
+        WordMatch { pattern, start: end - self.lengths[pattern], end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn words(matches: &[WordMatch], patterns: &[&'static str]) -> Vec<&'static str> {
+        matches.iter().map(|word_match| patterns[word_match.pattern]).collect()
+    }
+
+    #[test]
+    fn it_reports_overlapping_matches() {
+        let patterns = ["one", "two", "eight"];
+        let scanner = WordScanner::new(&patterns);
+        let result = scanner.find_overlapping("eightwone");
+        assert_eq!(words(&result, &patterns), vec!["eight", "two", "one"]);
+        assert_eq!(result[1], WordMatch { pattern: 1, start: 4, end: 7 });
+    }
+
+    #[test]
+    fn it_finds_the_earliest_starting_match() {
+        let patterns = ["bc", "abcd"];
+        let scanner = WordScanner::new(&patterns);
+        let result = scanner.first_match("xabcd");
+        assert_eq!(result, Some(WordMatch { pattern: 1, start: 1, end: 5 }));
+    }
+
+    #[test]
+    fn it_finds_the_latest_starting_match() {
+        let patterns = ["one", "two", "eight"];
+        let scanner = WordScanner::new(&patterns);
+        assert_eq!(scanner.last_match("twoneightwo"), Some(WordMatch { pattern: 1, start: 8, end: 11 }));
+        assert_eq!(scanner.last_match("zoneight234"), Some(WordMatch { pattern: 2, start: 3, end: 8 }));
+    }
+
+    #[test]
+    fn it_finds_nothing_without_matches() {
+        let scanner = WordScanner::new(&["one", ""]);
+        assert_eq!(scanner.find_overlapping("two"), vec![]);
+        assert_eq!(scanner.first_match("two"), None);
+        assert_eq!(scanner.last_match("two"), None);
+    }
+
+    fn naive_matches(patterns: &[String], haystack: &str) -> Vec<WordMatch> {
+        let mut result = Vec::new();
+        for (pattern, word) in patterns.iter().enumerate() {
+            for start in 0..haystack.len() {
+                if !word.is_empty() && haystack[start..].starts_with(word.as_str()) {
+                    result.push(WordMatch { pattern, start, end: start + word.len() });
+                }
+            }
+        }
+        result
+    }
+
+    proptest! {
+        #[test]
+        fn it_agrees_with_a_naive_search(patterns in prop::collection::vec("[ab]{0,4}", 1..6), haystack in "[abc]{0,30}") {
+            let scanner = WordScanner::new(&patterns);
+            let mut expected = naive_matches(&patterns, &haystack);
+            let key = |word_match: &WordMatch| (word_match.end, word_match.start, word_match.pattern);
+            expected.sort_by_key(key);
+            let mut found = scanner.find_overlapping(&haystack);
+            prop_assert!(found.windows(2).all(|pair| (pair[0].end, pair[0].start) <= (pair[1].end, pair[1].start)));
+            found.sort_by_key(key);
+            prop_assert_eq!(found, expected.clone());
+
+            let first = expected.iter().map(|word_match| word_match.start).min();
+            prop_assert_eq!(scanner.first_match(&haystack).map(|word_match| word_match.start), first);
+            let last = expected.iter().map(|word_match| (word_match.start, word_match.end)).max();
+            prop_assert_eq!(scanner.last_match(&haystack).map(|word_match| (word_match.start, word_match.end)), last);
+        }
+    }
+}
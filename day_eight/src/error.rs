@@ -0,0 +1,28 @@
+use std::{error::Error, fmt::{self, Display}};
+
+use common::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    Parse(ParseError),
+    UnknownNode(String),
+    NeverArrives { from: String, to: String },
+}
+
+impl Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::Parse(error) => write!(f, "{error}"),
+            RouteError::UnknownNode(node) => write!(f, "no node named {node:?}"),
+            RouteError::NeverArrives { from, to } => write!(f, "following the instructions from {from} never reaches {to}"),
+        }
+    }
+}
+
+impl Error for RouteError {}
+
+impl From<ParseError> for RouteError {
+    fn from(error: ParseError) -> RouteError {
+        RouteError::Parse(error)
+    }
+}
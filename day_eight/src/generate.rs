@@ -0,0 +1,52 @@
+use common::Rng;
+
+const MAX_NODES: usize = 26 * 26 * 26;
+
+/// A network of `size` nodes (at least two, at most every three letter
+/// name) and a matching instruction list. The nodes from `AAA` to `ZZZ`
+/// form a chain where each node either steps forward or waits, and the
+/// instructions contain both `L` and `R`, so `ZZZ` is always reached.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.clamp(2, MAX_NODES);
+    let mut names: Vec<_> = (0..MAX_NODES)
+        .map(|index| {
+            let letter = |shift: usize| (b'A' + (index / shift % 26) as u8) as char;
+            [letter(26 * 26), letter(26), letter(1)].iter().collect::<String>()
+        })
+        .filter(|name| name != "AAA" && name != "ZZZ")
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(size - 2);
+
+    let chain_length = rng.between(0, names.len() as u64) as usize;
+    let mut chain = vec!["AAA".to_string()];
+    chain.extend_from_slice(&names[..chain_length]);
+    chain.push("ZZZ".to_string());
+
+    let mut routes = Vec::new();
+    for pair in chain.windows(2) {
+        let (current, next) = (&pair[0], &pair[1]);
+        let route = match rng.below(3) {
+            0 => (next, current),
+            1 => (current, next),
+            _ => (next, next),
+        };
+        routes.push(format!("{current} = ({}, {})", route.0, route.1));
+    }
+    routes.push("ZZZ = (ZZZ, ZZZ)".to_string());
+    for name in &names[chain_length..] {
+        let left = rng.choose(&names);
+        let right = rng.choose(&names);
+        routes.push(format!("{name} = ({left}, {right})"));
+    }
+    rng.shuffle(&mut routes);
+
+    let mut instructions: Vec<_> = (0..rng.between(2, 300)).map(|_| *rng.choose(&['L', 'R'])).collect();
+    instructions[0] = 'L';
+    instructions[1] = 'R';
+    rng.shuffle(&mut instructions);
+
+    let instructions: String = instructions.into_iter().collect();
+    format!("{instructions}\n\n{}\n", routes.join("\n"))
+}
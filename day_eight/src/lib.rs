@@ -1,55 +1,34 @@
-use std::collections::HashMap;
-use lazy_static::lazy_static;
-use regex::Regex;
+mod error;
+mod generate;
+mod network;
+pub mod reference;
 
-lazy_static! {
-    static ref ROUTE_PATTERN: Regex = Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
-}
+pub use error::RouteError;
+pub use generate::generate;
+pub use network::Network;
+
+use common::normalise;
 
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
-    }
-
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
     }
-}
 
-fn part_one(input: &str) -> u64{
-    let mut lines = input.lines();
-    let pattern_chars: Vec<_> = lines.next().unwrap().chars().collect();
-    let pattern_repeat = pattern_chars.len() as u64;
-    lines.next();
-    let mut routes = HashMap::new();
-    for line in lines {
-        let parts = ROUTE_PATTERN.captures(line).unwrap();
-        routes.insert(parts[1].to_string(), [parts[2].to_string(), parts[3].to_string()]);
-    }
-    let mut current_point = "AAA".to_string();
-    let mut current_steps = 0u64;
-    while current_point != "ZZZ" {
-        let pattern_index: usize = (current_steps % pattern_repeat).try_into().unwrap();
-        let next_index: usize = if pattern_chars[pattern_index] == 'L' {
-            0
-        } else {
-            1
-        };
-        current_point = routes.get(&current_point).unwrap()[next_index].clone();
-        current_steps += 1;
+    fn part_two(&self, _input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(common::Answer::Unsolved)
     }
-    current_steps
 }
 
-fn part_two(input: &str) -> u64{
-    0
+fn part_one(input: &str) -> Result<u64, RouteError> {
+    Network::parse_new(&normalise(input))?.steps("AAA", "ZZZ")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::ParseError;
     use indoc::indoc;
 
     fn test_input_one() -> &'static str {
@@ -75,12 +54,38 @@ mod tests {
     #[test]
     fn it_passes_first_example() {
         let result = part_one(test_input_one());
-        assert_eq!(result, 2);
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn it_passes_second_example() {
         let result = part_one(test_input_two());
-        assert_eq!(result, 6);
-    }    
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn it_reports_malformed_routes() {
+        let result = part_one("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(result, Err(RouteError::Parse(ParseError::new(3, 8, "no node named \"BBB\""))));
+        let result = part_one("LX\n\nAAA = (ZZZ, ZZZ)");
+        assert_eq!(result, Err(RouteError::Parse(ParseError::new(1, 2, "invalid instruction 'X'"))));
+        let result = part_one("L\n\nAAA = (AAA, AAA\n");
+        assert_eq!(result, Err(RouteError::Parse(ParseError::new(3, 16, "expected ')'"))));
+        let result = part_one("LR\nAAA = (AAA, AAA)");
+        assert_eq!(result, Err(RouteError::Parse(ParseError::new(2, 1, "unexpected \"AAA\""))));
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n \r\n", test_input_two().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(6));
+    }
+
+    #[test]
+    fn it_stops_when_the_route_goes_round_in_circles() {
+        let result = part_one("LR\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(result, Err(RouteError::NeverArrives { from: "AAA".to_string(), to: "ZZZ".to_string() }));
+        let result = part_one("L\n\nBBB = (BBB, BBB)");
+        assert_eq!(result, Err(RouteError::UnknownNode("AAA".to_string())));
+    }
 }
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use common::{parser::{delimited, end, line, separated, spaces, tag, word, Input, ParseResult}, ParseError, Sections};
+
+use crate::RouteError;
+
+/// The left/right instructions and the node each one leads to, checked on
+/// parsing so that every node a route points at exists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    instructions: Vec<usize>,
+    routes: HashMap<String, [String; 2]>,
+}
+
+impl Network {
+    pub fn parse_new(input: &str) -> Result<Network, ParseError> {
+        let sections = Sections::split(input)?;
+        let (instruction_text, rest) = line(word)(sections.header)?;
+        end(rest)?;
+        let instructions = instruction_text.char_indices()
+            .map(|(index, instruction)| match instruction {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(sections.header.error_at(&instruction_text[index..], format!("invalid instruction {instruction:?}"))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let [routes_section] = sections.sections[..] else {
+            return Err(sections.header.error("expected one block of routes after a blank line"));
+        };
+
+        let mut routes = HashMap::new();
+        let mut targets = Vec::new();
+        for route_line in routes_section.block.lines() {
+            let ((node, [left, right]), _) = line(route)(route_line)?;
+            if routes.contains_key(node) {
+                return Err(route_line.error(format!("node {node:?} defined twice")));
+            }
+            targets.push((route_line, left));
+            targets.push((route_line, right));
+            routes.insert(node.to_string(), [left.to_string(), right.to_string()]);
+        }
+        if let Some((route_line, target)) = targets.iter().find(|(_, target)| !routes.contains_key(*target)) {
+            return Err(route_line.error_at(target, format!("no node named {target:?}")));
+        }
+        Ok(Network { instructions, routes })
+    }
+
+    /// The number of steps taken to get from `from` to `to`. Once the walk
+    /// has been at every node at every point in the instructions without
+    /// arriving, it can only repeat itself.
+    pub fn steps(&self, from: &str, to: &str) -> Result<u64, RouteError> {
+        for node in [from, to] {
+            if !self.routes.contains_key(node) {
+                return Err(RouteError::UnknownNode(node.to_string()));
+            }
+        }
+        let limit = self.routes.len().saturating_mul(self.instructions.len()) as u64;
+        let mut current_point = from;
+        let mut current_steps = 0u64;
+        while current_point != to {
+            if current_steps >= limit {
+                return Err(RouteError::NeverArrives { from: from.to_string(), to: to.to_string() });
+            }
+            let instruction = self.instructions[(current_steps % self.instructions.len() as u64) as usize];
+            current_point = &self.routes[current_point][instruction];
+            current_steps += 1;
+        }
+        Ok(current_steps)
+    }
+}
+
+/// A `<node> = (<left>, <right>)` line.
+fn route(input: Input<'_>) -> ParseResult<'_, (&str, [&str; 2])> {
+    let (node, input) = word(input)?;
+    let (_, input) = spaces(input)?;
+    let (_, input) = tag("=")(input)?;
+    let (_, input) = spaces(input)?;
+    let (targets, rest) = delimited("(", separated(word, ","), ")")(input)?;
+    let [left, right] = targets[..] else {
+        return Err(input.error(format!("expected two nodes, found {}", targets.len())));
+    };
+    Ok(((node, [left, right]), rest))
+}
@@ -0,0 +1,18 @@
+/// Follows the instructions one step at a time, looking each node up by
+/// scanning the whole network.
+pub fn part_one(input: &str) -> u64 {
+    let mut lines = input.lines();
+    let instructions: Vec<_> = lines.next().unwrap().chars().collect();
+    let network: Vec<(&str, &str, &str)> = lines
+        .filter(|line| !line.is_empty())
+        .map(|line| (&line[0..3], &line[7..10], &line[12..15]))
+        .collect();
+    let mut node = "AAA";
+    let mut steps = 0;
+    while node != "ZZZ" {
+        let (_, left, right) = network.iter().find(|(name, _, _)| *name == node).unwrap();
+        node = if instructions[steps % instructions.len()] == 'L' { left } else { right };
+        steps += 1;
+    }
+    steps as u64
+}
@@ -0,0 +1,45 @@
+use common::Rng;
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+const SEED_RANGES: usize = 10;
+const SPAN: u64 = 1 << 32;
+
+/// An almanac of ten seed ranges and seven maps of `size` ranges each. Each
+/// map shuffles contiguous blocks of a random span, as the puzzle's do.
+pub fn generate(size: usize, seed: u64) -> String {
+    generate_within(size, seed, SPAN)
+}
+
+/// As `generate`, but with every number below `span`, so that the seed
+/// ranges are small enough to expand one seed at a time.
+pub fn generate_within(size: usize, seed: u64, span: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let seeds: Vec<_> = (0..SEED_RANGES)
+    .map(|_| {
+        let start = rng.below(span);
+        format!("{start} {}", rng.between(1, (span / 16).max(1)))
+    })
+    .collect();
+    let mut result = format!("seeds: {}\n", seeds.join(" "));
+
+    for names in CATEGORIES.windows(2) {
+        let mut cuts: Vec<_> = (0..=size).map(|_| rng.below(span)).collect();
+        cuts.sort();
+        cuts.dedup();
+        let mut blocks: Vec<_> = cuts.windows(2).map(|cut| (cut[0], cut[1] - cut[0])).collect();
+        rng.shuffle(&mut blocks);
+        let mut destination = cuts[0];
+        let mut lines = Vec::new();
+        for (source, length) in &blocks {
+            lines.push(format!("{destination} {source} {length}"));
+            destination += length;
+        }
+        rng.shuffle(&mut lines);
+        result.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+        for line in lines {
+            result.push_str(&line);
+            result.push('\n');
+        }
+    }
+    result
+}
@@ -1,23 +1,23 @@
-use std::{ops::Range, collections::{HashMap, VecDeque}, str::Lines, cmp::Ordering};
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::{ops::Range, collections::{HashMap, HashSet, VecDeque}, cmp::Ordering};
+use common::{parser::{end, integer, labelled, line, list, spaces, tag, word, Input, ParseResult}, normalise, ParseError, Section, Sections};
+
+mod generate;
+pub mod reference;
+
+pub use generate::{generate, generate_within};
 
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
     }
 }
 
-lazy_static! {
-    static ref MAP_PATTERN: Regex = Regex::new(r"(\w+)-to-(\w+) map:").unwrap();
-}
-
 fn combine_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
     let mut result = Vec::new();
     ranges.sort_by(|a,b| {
@@ -27,7 +27,7 @@ fn combine_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
     for range in ranges {
         if let Some(ref current) = current_range {
             if current.end == range.start || current.contains(&range.start) {
-                current_range = Some(current.start..range.end);
+                current_range = Some(current.start..current.end.max(range.end));
             } else {
                 result.push(current.clone());
                 current_range = Some(range);
@@ -48,19 +48,55 @@ fn split_range(range: &Range<u64>, split_index: &u64) -> (Range<u64>, Range<u64>
     (range.start..*split_index, *split_index..range.end)
 }
 
-struct Almanac {
+/// The seeds to plant and the maps that lead from one category to the next,
+/// checked on parsing to form an unbroken chain from seed to location.
+pub struct Almanac {
+    seeds: Vec<u64>,
     maps: HashMap<String, RangeMap>,
 }
 
 impl Almanac {
-    fn parse_new(lines: &mut Lines) -> Almanac {
+    pub fn parse_new(input: &str) -> Result<Almanac, ParseError> {
+        let sections = Sections::split(input)?;
+        let (seeds, rest) = line(labelled("seeds:", list(integer::<u64>)))(sections.header)?;
+        end(rest)?;
+        if seeds.is_empty() {
+            return Err(sections.header.error("expected at least one seed"));
+        }
         let mut maps = HashMap::new();
-        let mut lines = lines.peekable();
-        while lines.peek().is_some() {
-            let map = RangeMap::parse_new(&mut lines);
+        for section in &sections.sections {
+            let map = RangeMap::parse_new(section)?;
+            if maps.contains_key(&map.source_name) {
+                return Err(section.block.error(format!("second map from {:?}", map.source_name)));
+            }
             maps.insert(map.source_name.clone(), map);
         }
-        Almanac { maps }
+        let almanac = Almanac { seeds, maps };
+        almanac.check_chain()?;
+        Ok(almanac)
+    }
+
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// Follows the maps from seed, so that converting a seed can neither
+    /// miss a map nor go round in circles.
+    fn check_chain(&self) -> Result<(), ParseError> {
+        let mut visited = HashSet::new();
+        let mut name = "seed";
+        let mut line = 1;
+        while name != "location" {
+            let Some(map) = self.maps.get(name) else {
+                return Err(ParseError::new(line, 1, format!("no map from {name:?}")));
+            };
+            if !visited.insert(name) {
+                return Err(ParseError::new(map.line, 1, format!("maps loop back to {name:?}")));
+            }
+            name = &map.dest_name;
+            line = map.line;
+        }
+        Ok(())
     }
 
     fn seed_to_location(&self, seed: &u64) -> u64 {
@@ -84,36 +120,43 @@ impl Almanac {
     }
 }
 
+#[derive(Debug)]
 struct RangeMap {
+    line: usize,
     source_name: String,
     dest_name: String,
     pairs: Vec<RangePair>,
 }
 
 impl RangeMap {
-    fn parse_new<'a>(lines: &mut impl Iterator<Item = &'a str>) -> RangeMap {
-        let descriptions = MAP_PATTERN.captures(lines.next().unwrap()).unwrap();
-        let source_name = &descriptions[1];
-        let dest_name = &descriptions[2];
-        let mut pairs = Vec::new();
-        while let Some(line) = lines.next() {
-            if line.is_empty() {
-                break;
-            }
-            pairs.push(RangePair::parse_new(line));
-        }
+    /// Parses a section named `<source>-to-<destination> map:` holding one
+    /// range per line.
+    fn parse_new(section: &Section<'_>) -> Result<RangeMap, ParseError> {
+        let Some(name) = section.name else {
+            return Err(section.block.error("expected '<source>-to-<destination> map:'"));
+        };
+        let ((source_name, dest_name), _) = line(map_name)(name)?;
+        let mut pairs = section.body.lines()
+            .map(|line| Ok((line, RangePair::parse_new(line)?)))
+            .collect::<Result<Vec<_>, ParseError>>()?;
         pairs.sort_by(|a,b| {
-            a.source.start.cmp(&b.source.start)
+            a.1.source.start.cmp(&b.1.source.start)
         });
-        RangeMap {
+        if let Some(overlap) = pairs.windows(2).find(|pair| pair[0].1.source.end > pair[1].1.source.start) {
+            let mut line_numbers = [overlap[0].0.position().0, overlap[1].0.position().0];
+            line_numbers.sort();
+            return Err(ParseError::new(line_numbers[1], 1, format!("source range overlaps the one on line {}", line_numbers[0])));
+        }
+        Ok(RangeMap {
+            line: name.position().0,
             source_name: source_name.to_string(),
             dest_name: dest_name.to_string(),
-            pairs
-        }
+            pairs: pairs.into_iter().map(|(_, pair)| pair).collect(),
+        })
     }
 
     fn to_destination(&self, input: &u64) -> u64 {
-        match self.find_containing_range(&input) {
+        match self.find_containing_range(input) {
             Ok(index) => self.pairs[index].to_destination(input),
             Err(_) => *input,
         }
@@ -132,25 +175,11 @@ impl RangeMap {
         let mut result: Vec<Range<u64>> = Vec::new();
         let mut current_ranges = VecDeque::new();
         for range in &input {
-            let start_range = self.find_containing_range(&range.start);
-            let mut current_index = match start_range {
-                Ok(index) => {
-                    current_ranges.push_back(range.clone());
-                    index
-                }
-                Err(index) => {
-                    if index == self.pairs.len() {
-                        result.push(range.clone());
-                        continue;
-                    }
-                    let (outside, inside) = split_range(range, &self.pairs[index].source.start);
-                    result.push(outside);
-                    current_ranges.push_back(inside);
-                    index
-                }
+            let mut current_index = match self.find_containing_range(&range.start) {
+                Ok(index) | Err(index) => index,
             };
-            while !current_ranges.is_empty() {
-                let next_range = current_ranges.pop_front().unwrap();
+            current_ranges.push_back(range.clone());
+            while let Some(next_range) = current_ranges.pop_front() {
                 if next_range.is_empty() {
                     break;
                 }
@@ -158,6 +187,13 @@ impl RangeMap {
                     result.push(next_range);
                     break;
                 }
+                let pair_start = self.pairs[current_index].source.start;
+                if next_range.start < pair_start {
+                    let (outside, inside) = split_range(&next_range, &pair_start.min(next_range.end));
+                    result.push(outside);
+                    current_ranges.push_back(inside);
+                    continue;
+                }
                 let converted_range = self.pairs[current_index].range_to_destination(&next_range);
                 match converted_range {
                     RangeConversion::Complete(range) => result.push(range),
@@ -186,12 +222,18 @@ struct RangePair {
 }
 
 impl RangePair {
-    fn parse_new(input: &str) -> RangePair {
-        let mut numbers = input.split_whitespace().map(str::parse::<u64>).map(Result::unwrap);
-        let destination = numbers.next().unwrap();
-        let source = numbers.next().unwrap();
-        let size = numbers.next().unwrap();
-        RangePair { source: source..source+size, destination: destination..destination+size }
+    fn parse_new(input: Input<'_>) -> Result<RangePair, ParseError> {
+        let (numbers, _) = line(list(integer::<u64>))(input)?;
+        let [destination, source, size] = numbers[..] else {
+            return Err(input.error("expected '<destination> <source> <length>'"));
+        };
+        if size == 0 {
+            return Err(input.error("range length must not be zero"));
+        }
+        let (Some(source_end), Some(destination_end)) = (source.checked_add(size), destination.checked_add(size)) else {
+            return Err(input.error(format!("range of length {size} overflows")));
+        };
+        Ok(RangePair { source: source..source_end, destination: destination..destination_end })
     }
 
     fn to_destination(&self, input: &u64) -> u64 {
@@ -204,9 +246,9 @@ impl RangePair {
         }
         if input.end > self.source.end {
             let (to_convert, remainder) = split_range(input, &self.source.end);
-            RangeConversion::Remainder(self.contained_range_to_destination(&&to_convert), remainder)
+            RangeConversion::Remainder(self.contained_range_to_destination(&to_convert), remainder)
         } else {
-            RangeConversion::Complete(self.contained_range_to_destination(&input))
+            RangeConversion::Complete(self.contained_range_to_destination(input))
         }
     }
 
@@ -215,41 +257,40 @@ impl RangePair {
     }
 }
 
-fn extract_seeds(lines: &mut Lines<'_>) -> Vec<u64> {
-    let initial_seeds: Vec<u64> = lines.next().unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(str::parse::<u64>)
-        .map(Result::unwrap)
-        .collect();
-    initial_seeds
-}
-
-fn parse_input(input: &str) -> (Vec<u64>, Almanac) {
-    let mut lines = input.lines();
-    let initial_seeds = extract_seeds(&mut lines);
-    lines.next();
-    let almanac = Almanac::parse_new(&mut lines);
-    (initial_seeds, almanac)
+fn map_name(input: Input<'_>) -> ParseResult<'_, (&str, &str)> {
+    let (source, input) = word(input)?;
+    let (_, input) = tag("-to-")(input)?;
+    let (destination, input) = word(input)?;
+    let (_, input) = spaces(input)?;
+    let (_, input) = tag("map")(input)?;
+    Ok(((source, destination), input))
 }
 
-fn part_one(input: &str) -> u64 {
-    let (initial_seeds, almanac) = parse_input(input);
-    initial_seeds.iter().map(|seed| {
+fn part_one(input: &str) -> Result<u64, ParseError> {
+    let almanac = Almanac::parse_new(&normalise(input))?;
+    Ok(almanac.seeds().iter().map(|seed| {
         almanac.seed_to_location(seed)
     })
-    .min().unwrap()
+    .min().unwrap())
 }
 
-fn part_two(input: &str) -> u64 {
-    let (initial_seeds, almanac) = parse_input(input);
-    let seed_ranges: Vec<_> = initial_seeds.chunks(2).map(|seed_count| {
-        seed_count[0]..(seed_count[0]+seed_count[1])
+fn part_two(input: &str) -> Result<u64, ParseError> {
+    let almanac = Almanac::parse_new(&normalise(input))?;
+    if almanac.seeds().len() % 2 != 0 {
+        return Err(ParseError::new(1, 1, "expected seeds in pairs of start and length"));
+    }
+    let seed_ranges = almanac.seeds().chunks(2).map(|seed_count| {
+        if seed_count[1] == 0 {
+            return Err(ParseError::new(1, 1, format!("seed range starting at {} is empty", seed_count[0])));
+        }
+        seed_count[0].checked_add(seed_count[1])
+            .map(|end| seed_count[0]..end)
+            .ok_or_else(|| ParseError::new(1, 1, format!("seed range starting at {} overflows", seed_count[0])))
     })
-    .collect();
-    almanac.seed_range_to_location(seed_ranges).iter()
+    .collect::<Result<Vec<_>, _>>()?;
+    Ok(almanac.seed_range_to_location(seed_ranges).iter()
     .map(|range| range.start)
-    .min().unwrap()
+    .min().unwrap())
 }
 
 #[cfg(test)]
@@ -257,6 +298,7 @@ mod tests {
     use super::*;
 
     use indoc::indoc;
+    use proptest::prelude::*;
 
     #[test]
     fn it_combines_adjecent_ranges() {
@@ -315,12 +357,113 @@ mod tests {
     #[test]
     fn it_finds_the_lowest_location_number() {
         let result = part_one(test_input());
-        assert_eq!(result, 35);
+        assert_eq!(result, Ok(35));
     }
 
     #[test]
     fn it_finds_the_lowest_location_in_ranges() {
         let result = part_two(test_input());
-        assert_eq!(result, 46);
+        assert_eq!(result, Ok(46));
+    }
+
+    #[test]
+    fn it_leaves_gaps_between_ranges_unmapped() {
+        let result = part_two(indoc! {"seeds: 15 20 2 3
+
+            seed-to-location map:
+            100 10 10
+            200 30 10"});
+        assert_eq!(result, Ok(2));
+        let map = RangeMap::parse_new(&Section::new(Input::new("a-to-b map:\n100 10 10\n200 30 10"))).unwrap();
+        let result = combine_ranges(map.ranges_to_destination(vec![2..5, 15..35]));
+        assert_eq!(result, vec![2..5, 20..30, 105..110, 200..205]);
+    }
+
+    #[test]
+    fn it_keeps_ranges_nested_inside_others() {
+        let result = combine_ranges(vec![0..10, 2..5]);
+        assert_eq!(result, vec![0..10]);
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", test_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(35));
+        assert_eq!(part_two(&input), Ok(46));
+    }
+
+    #[test]
+    fn it_reports_malformed_ranges() {
+        let result = part_one("seeds: 1\n\nseed-to-location map:\n1 2\n");
+        assert_eq!(result, Err(ParseError::new(4, 1, "expected '<destination> <source> <length>'")));
+        let result = part_one("seeds: 1\n\nseed-to-location map:\n1 x 3\n");
+        assert_eq!(result, Err(ParseError::new(4, 3, "invalid number \"x\"")));
+        let result = part_one("seeds: 1\n\nseed-to-location map:\n1 10 5\n20 12 5");
+        assert_eq!(result, Err(ParseError::new(5, 1, "source range overlaps the one on line 4")));
+    }
+
+    #[test]
+    fn it_reports_empty_seed_ranges() {
+        let result = part_two("seeds: 1 2 5 0\n\nseed-to-location map:\n1 2 3\n");
+        assert_eq!(result, Err(ParseError::new(1, 1, "seed range starting at 5 is empty")));
+    }
+
+    #[test]
+    fn it_rejects_maps_that_never_reach_location() {
+        let result = part_one("seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-water map:\n1 2 3");
+        assert_eq!(result, Err(ParseError::new(6, 1, "no map from \"water\"")));
+        let result = part_one("seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n1 2 3");
+        assert_eq!(result, Err(ParseError::new(3, 1, "maps loop back to \"seed\"")));
+    }
+
+    fn range_map() -> impl Strategy<Value = RangeMap> {
+        prop::collection::vec((0u64..20, 1u64..20, 0u64..500), 0..6).prop_map(|layout| {
+            let mut start = 0;
+            let pairs = layout.into_iter().map(|(gap, size, destination)| {
+                start += gap;
+                let pair = RangePair { source: start..start + size, destination: destination..destination + size };
+                start += size;
+                pair
+            })
+            .collect();
+            RangeMap { line: 1, source_name: "a".to_string(), dest_name: "b".to_string(), pairs }
+        })
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<u64>>> {
+        prop::collection::vec((0u64..300, 1u64..30).prop_map(|(start, size)| start..start + size), 1..6)
+    }
+
+    fn points(ranges: &[Range<u64>]) -> Vec<u64> {
+        let mut result: Vec<_> = ranges.iter().flat_map(Range::clone).collect();
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    proptest! {
+        #[test]
+        fn range_conversion_agrees_with_single_values(map in range_map(), input in ranges()) {
+            let mut expected: Vec<_> = points(&input).iter().map(|value| map.to_destination(value)).collect();
+            expected.sort();
+            expected.dedup();
+            prop_assert_eq!(points(&map.ranges_to_destination(input)), expected);
+        }
+
+        #[test]
+        fn combining_ranges_keeps_the_same_values(input in ranges()) {
+            let combined = combine_ranges(input.clone());
+            prop_assert_eq!(points(&combined), points(&input));
+            prop_assert!(combined.windows(2).all(|pair| pair[0].end < pair[1].start));
+        }
+
+        #[test]
+        fn combining_ranges_is_idempotent_and_order_independent(input in ranges(), seed in any::<u64>()) {
+            let combined = combine_ranges(input.clone());
+            prop_assert_eq!(combine_ranges(combined.clone()), combined.clone());
+            let mut shuffled = input;
+            common::Rng::new(seed).shuffle(&mut shuffled);
+            prop_assert_eq!(combine_ranges(shuffled), combined);
+        }
     }
 }
@@ -0,0 +1,59 @@
+struct Map {
+    destination: String,
+    ranges: Vec<(u64, u64, u64)>,
+}
+
+fn parse(input: &str) -> (Vec<u64>, Vec<(String, Map)>) {
+    let mut sections = input.split("\n\n");
+    let seeds = sections.next().unwrap()
+        .split_whitespace()
+        .skip(1)
+        .map(|seed| seed.parse().unwrap())
+        .collect();
+    let maps = sections
+    .map(|section| {
+        let mut lines = section.lines();
+        let (source, rest) = lines.next().unwrap().split_once("-to-").unwrap();
+        let destination = rest.trim_end_matches(" map:").to_string();
+        let ranges = lines
+        .map(|line| {
+            let numbers: Vec<u64> = line.split_whitespace().map(|number| number.parse().unwrap()).collect();
+            (numbers[0], numbers[1], numbers[2])
+        })
+        .collect();
+        (source.to_string(), Map { destination, ranges })
+    })
+    .collect();
+    (seeds, maps)
+}
+
+fn location(seed: u64, maps: &[(String, Map)]) -> u64 {
+    let mut category = "seed";
+    let mut value = seed;
+    while category != "location" {
+        let (_, map) = maps.iter().find(|(source, _)| source == category).unwrap();
+        for (destination, source, length) in &map.ranges {
+            if value >= *source && value < source + length {
+                value = value - source + destination;
+                break;
+            }
+        }
+        category = &map.destination;
+    }
+    value
+}
+
+/// Converts each seed by checking every range of every map.
+pub fn part_one(input: &str) -> u64 {
+    let (seeds, maps) = parse(input);
+    seeds.iter().map(|seed| location(*seed, &maps)).min().unwrap()
+}
+
+/// Converts every single seed in every seed range.
+pub fn part_two(input: &str) -> u64 {
+    let (seeds, maps) = parse(input);
+    seeds.chunks(2)
+    .flat_map(|pair| pair[0]..pair[0] + pair[1])
+    .map(|seed| location(seed, &maps))
+    .min().unwrap()
+}
@@ -0,0 +1,82 @@
+use std::{cmp::Reverse, fmt::{self, Display}, ops::RangeInclusive};
+
+use crate::{CardError, Scratchcard, Tally};
+
+const SUMMARY_SIZE: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardTrace<T: Tally = u64> {
+    pub id: usize,
+    pub matches: usize,
+    pub copies: T,
+    /// Ids of the later cards this card wins a copy of, once per copy held.
+    pub awarded: Option<RangeInclusive<usize>>,
+}
+
+impl<T: Tally> CardTrace<T> {
+    pub fn copies_awarded(&self) -> T {
+        let Some(awarded) = &self.awarded else {
+            return T::ZERO;
+        };
+        let count = T::from_usize(awarded.clone().count()).unwrap_or(T::ZERO);
+        count.saturating_mul(self.copies)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CascadeTrace<T: Tally = u64> {
+    pub cards: Vec<CardTrace<T>>,
+    pub total: T,
+}
+
+impl<T: Tally> CascadeTrace<T> {
+    pub fn new(cards: &[Scratchcard]) -> Result<CascadeTrace<T>, CardError> {
+        let overflow = |card: &Scratchcard| CardError::Overflow { card: card.id, width: T::NAME };
+        let mut copies = vec![T::ONE; cards.len()];
+        let mut traces = Vec::with_capacity(cards.len());
+        let mut total = T::ZERO;
+        for (index, card) in cards.iter().enumerate() {
+            let last_won = (index + card.matches()).min(cards.len() - 1);
+            for later in index + 1..=last_won {
+                copies[later] = copies[later].checked_add(copies[index])
+                    .ok_or_else(|| overflow(&cards[later]))?;
+            }
+            total = total.checked_add(copies[index]).ok_or_else(|| overflow(card))?;
+            traces.push(CardTrace {
+                id: card.id,
+                matches: card.matches(),
+                copies: copies[index],
+                awarded: (last_won > index).then(|| cards[index + 1].id..=cards[last_won].id),
+            });
+        }
+        Ok(CascadeTrace { cards: traces, total })
+    }
+
+    /// The cards that hand out the most copies, largest first.
+    pub fn fastest_growth(&self, count: usize) -> Vec<&CardTrace<T>> {
+        let mut cards: Vec<_> = self.cards.iter().filter(|card| card.copies_awarded() > T::ZERO).collect();
+        cards.sort_by_key(|card| Reverse(card.copies_awarded()));
+        cards.truncate(count);
+        cards
+    }
+}
+
+impl<T: Tally> Display for CascadeTrace<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>6} {:>8} {:>10}  awards", "card", "matches", "copies")?;
+        for card in &self.cards {
+            let awarded = match &card.awarded {
+                Some(awarded) if awarded.start() == awarded.end() => format!("{}", awarded.start()),
+                Some(awarded) => format!("{}-{}", awarded.start(), awarded.end()),
+                None => "-".to_string(),
+            };
+            writeln!(f, "{:>6} {:>8} {:>10}  {awarded}", card.id, card.matches, card.copies)?;
+        }
+        writeln!(f, "Total cards: {}", self.total)?;
+        writeln!(f, "Fastest growth:")?;
+        for card in self.fastest_growth(SUMMARY_SIZE) {
+            writeln!(f, "  card {} awards {} copies", card.id, card.copies_awarded())?;
+        }
+        Ok(())
+    }
+}
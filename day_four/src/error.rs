@@ -0,0 +1,26 @@
+use std::{error::Error, fmt::{self, Display}};
+
+use common::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardError {
+    Parse(ParseError),
+    Overflow { card: usize, width: &'static str },
+}
+
+impl Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardError::Parse(error) => write!(f, "{error}"),
+            CardError::Overflow { card, width } => write!(f, "card {card}: total overflows {width}"),
+        }
+    }
+}
+
+impl Error for CardError {}
+
+impl From<ParseError> for CardError {
+    fn from(error: ParseError) -> CardError {
+        CardError::Parse(error)
+    }
+}
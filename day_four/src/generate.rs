@@ -0,0 +1,31 @@
+use common::Rng;
+
+const WINNING: usize = 10;
+const HELD: usize = 25;
+
+/// `size` cards of ten winning and 25 held numbers. Match counts average
+/// below one, and never reach past the last card, so part two stays
+/// bounded however many cards there are.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let id_width = size.to_string().len();
+    let mut result = String::new();
+    for id in 1..=size {
+        let mut numbers: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, others) = numbers.split_at(WINNING);
+        let matches = match rng.below(10) {
+            0..=6 => 0,
+            7 | 8 => 1,
+            _ => rng.between(2, WINNING as u64) as usize,
+        }
+        .min(size - id);
+        let mut held: Vec<_> = winning[..matches].iter().chain(&others[..HELD - matches]).copied().collect();
+        rng.shuffle(&mut held);
+        let format_numbers = |numbers: &[u32]| {
+            numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ")
+        };
+        result.push_str(&format!("Card {id:>id_width$}: {} | {}\n", format_numbers(winning), format_numbers(&held)));
+    }
+    result
+}
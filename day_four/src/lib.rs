@@ -1,54 +1,102 @@
-use std::collections::{HashSet, HashMap};
+mod cascade;
+mod error;
+mod generate;
+pub mod reference;
+mod scratchcard;
+mod tally;
+
+pub use cascade::{CardTrace, CascadeTrace};
+pub use error::CardError;
+pub use generate::generate;
+pub use scratchcard::{parse_cards, read_cards, Scratchcard};
+pub use tally::Tally;
+
+use std::{collections::VecDeque, error::Error, io::BufRead};
+
+use common::normalise;
 
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
     }
-}
 
-const MARKERS: [char; 2] = [':','|'];
+    fn streaming(&self) -> Option<&dyn common::StreamingSolution> {
+        Some(self)
+    }
+}
 
-fn part_one(input: &str) -> u32 {
-    input.lines()
-    .map(|line| {
-        let results = count_winners(line);
-        match results {
-            0 => 0,
-            _ => 2u32.pow((results-1).try_into().unwrap())
+impl common::StreamingSolution for Solution {
+    fn part_one(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        let mut total = 0u64;
+        for card in read_cards(input) {
+            let card = card?;
+            total = total.checked_add(card_points(&card)?)
+                .ok_or(CardError::Overflow { card: card.id, width: u64::NAME })?;
         }
-    })
-    .sum()
+        Ok(total.into())
+    }
+
+    fn part_two(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        Ok(count_cards_stream::<u64>(input)?.into())
+    }
 }
 
-fn part_two(input: &str) -> u32 {
-    let mut extra_cards: HashMap<usize, u32> = HashMap::new();    
-    input.lines().enumerate()
-    .map(|(index, line)| {
-        let results = count_winners(line);
-        let current_cards = match extra_cards.get(&index) {
-            Some(extra) => extra + 1,
-            None => 1
+pub fn total_points<T: Tally>(cards: &[Scratchcard]) -> Result<T, CardError> {
+    let mut total = T::ZERO;
+    for card in cards {
+        total = total.checked_add(card_points(card)?)
+            .ok_or(CardError::Overflow { card: card.id, width: T::NAME })?;
+    }
+    Ok(total)
+}
+
+fn card_points<T: Tally>(card: &Scratchcard) -> Result<T, CardError> {
+    match card.matches() {
+        0 => Ok(T::ZERO),
+        matches => T::checked_pow2(matches - 1).ok_or(CardError::Overflow { card: card.id, width: T::NAME }),
+    }
+}
+
+pub fn total_cards<T: Tally>(cards: &[Scratchcard]) -> Result<T, CardError> {
+    Ok(CascadeTrace::<T>::new(cards)?.total)
+}
+
+/// Counts the scratchcards as `total_cards` does, reading one card at a time
+/// and keeping only the copies won of the cards still to come. A count that
+/// has already overflowed is held as `None` until its card is read.
+pub fn count_cards_stream<T: Tally>(input: impl BufRead) -> Result<T, Box<dyn Error>> {
+    let mut upcoming: VecDeque<Option<T>> = VecDeque::new();
+    let mut total = T::ZERO;
+    for card in read_cards(input) {
+        let card = card?;
+        let overflow = || CardError::Overflow { card: card.id, width: T::NAME };
+        let copies = match upcoming.pop_front() {
+            Some(won) => won.and_then(|won| won.checked_add(T::ONE)).ok_or_else(overflow)?,
+            None => T::ONE,
         };
-        for i in 1..=results {
-            *extra_cards.entry(index + i).or_insert(0) += current_cards;
+        if upcoming.len() < card.matches() {
+            upcoming.resize(card.matches(), Some(T::ZERO));
         }
-        current_cards
-    })
-    .sum()
+        for won in upcoming.iter_mut().take(card.matches()) {
+            *won = won.and_then(|won| won.checked_add(copies));
+        }
+        total = total.checked_add(copies).ok_or_else(overflow)?;
+    }
+    Ok(total)
 }
 
-fn count_winners(line: &str) -> usize {
-    let parts: Vec<_> = line.split(MARKERS).collect();
-    let winning_numbers: HashSet<u32> = HashSet::from_iter(parts[1].split_whitespace().map(str::parse::<u32>).map(Result::unwrap));
-    parts[2].split_whitespace().map(str::parse::<u32>).filter(|num| {
-        winning_numbers.contains(num.as_ref().unwrap())
-    }).count()
+fn part_one(input: &str) -> Result<u64, CardError> {
+    total_points(&parse_cards(&normalise(input))?)
+}
+
+fn part_two(input: &str) -> Result<u64, CardError> {
+    total_cards(&parse_cards(&normalise(input))?)
 }
 
 #[cfg(test)]
@@ -69,12 +117,73 @@ mod tests {
     #[test]
     fn it_totals_the_winnings() {
         let result = part_one(test_input());
-        assert_eq!(result, 13);
+        assert_eq!(result, Ok(13));
     }
 
     #[test]
     fn it_totals_the_amount_of_scratchcards() {
         let result = part_two(test_input());
-        assert_eq!(result, 30);
+        assert_eq!(result, Ok(30));
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", test_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(13));
+        assert_eq!(part_two(&input), Ok(30));
+    }
+
+    #[test]
+    fn it_traces_the_scratchcard_cascade() {
+        let cards = parse_cards(test_input()).unwrap();
+        let trace = CascadeTrace::<u64>::new(&cards).unwrap();
+        let copies: Vec<_> = trace.cards.iter().map(|card| card.copies).collect();
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(trace.cards[0].awarded, Some(2..=5));
+        assert_eq!(trace.cards[4].awarded, None);
+        let fastest: Vec<_> = trace.fastest_growth(2).iter().map(|card| card.id).collect();
+        assert_eq!(fastest, vec![3, 4]);
+    }
+
+    fn synthetic_cards(count: usize, matches: usize) -> String {
+        let numbers: Vec<_> = (1..=matches).map(|number| (number % 100).to_string()).collect();
+        let winning = numbers[..matches.min(99)].join(" ");
+        let held = numbers.join(" ");
+        (1..=count).map(|id| format!("Card {id}: {winning} | {held}\n")).collect()
+    }
+
+    #[test]
+    fn it_reports_points_overflow() {
+        let cards = parse_cards(&synthetic_cards(1, 40)).unwrap();
+        assert_eq!(total_points::<u32>(&cards), Err(CardError::Overflow { card: 1, width: "u32" }));
+        assert_eq!(total_points::<u64>(&cards), Ok(1 << 39));
+        let cards = parse_cards(&synthetic_cards(1, 130)).unwrap();
+        assert_eq!(total_points::<u64>(&cards), Err(CardError::Overflow { card: 1, width: "u64" }));
+        assert_eq!(total_points::<u128>(&cards), Err(CardError::Overflow { card: 1, width: "u128" }));
+        let cards = parse_cards(&synthetic_cards(3, 100)).unwrap();
+        assert_eq!(total_points::<u128>(&cards), Ok(3 << 98));
+    }
+
+    #[test]
+    fn it_reports_card_count_overflow() {
+        let cards = parse_cards(&synthetic_cards(40, 40)).unwrap();
+        assert_eq!(total_cards::<u32>(&cards), Err(CardError::Overflow { card: 33, width: "u32" }));
+        assert_eq!(total_cards::<u64>(&cards), Ok((1 << 40) - 1));
+        let cards = parse_cards(&synthetic_cards(100, 100)).unwrap();
+        assert_eq!(total_cards::<u64>(&cards), Err(CardError::Overflow { card: 65, width: "u64" }));
+        assert_eq!(total_cards::<u128>(&cards), Ok((1 << 100) - 1));
+    }
+
+    #[test]
+    fn it_counts_a_stream_of_cards() {
+        assert_eq!(count_cards_stream::<u64>(test_input().as_bytes()).unwrap(), 30);
+        let input = generate(500, 7);
+        assert_eq!(count_cards_stream::<u64>(input.as_bytes()).ok(), part_two(&input).ok());
+        let input = synthetic_cards(40, 40);
+        let result = count_cards_stream::<u32>(input.as_bytes()).map_err(|error| error.to_string());
+        assert_eq!(result, Err(CardError::Overflow { card: 33, width: "u32" }.to_string()));
+        assert_eq!(count_cards_stream::<u64>(input.as_bytes()).unwrap(), (1 << 40) - 1);
+        let result = count_cards_stream::<u64>("Card 1: 1 | 1\nCard 3: 2 | 2".as_bytes()).map_err(|error| error.to_string());
+        assert_eq!(result, Err("line 2, column 1: expected card 2, found card 3".to_string()));
     }
 }
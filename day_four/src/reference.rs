@@ -0,0 +1,35 @@
+fn matches(input: &str) -> Vec<usize> {
+    input.lines()
+    .map(|line| {
+        let (_, numbers) = line.split_once(':').unwrap();
+        let (winning, held) = numbers.split_once('|').unwrap();
+        let winning: Vec<_> = winning.split_whitespace().collect();
+        held.split_whitespace().filter(|number| winning.contains(number)).count()
+    })
+    .collect()
+}
+
+/// Doubles the score once for every match after the first.
+pub fn part_one(input: &str) -> u64 {
+    matches(input).iter()
+    .map(|matches| {
+        let mut score = 0;
+        for _ in 0..*matches {
+            score = if score == 0 { 1 } else { score * 2 };
+        }
+        score
+    })
+    .sum()
+}
+
+/// Scratches every copy of every card one at a time.
+pub fn part_two(input: &str) -> u64 {
+    let matches = matches(input);
+    let mut pile: Vec<usize> = (0..matches.len()).collect();
+    let mut scratched = 0;
+    while let Some(card) = pile.pop() {
+        scratched += 1;
+        pile.extend((card + 1..=card + matches[card]).filter(|won| *won < matches.len()));
+    }
+    scratched
+}
@@ -0,0 +1,118 @@
+use std::{error::Error, io::BufRead};
+
+use common::{parser::{end, integer, labelled, list, tag, try_map, Input}, InputLines, ParseError};
+
+const MAX_NUMBER: u32 = 100;
+
+/// A card parsed once, with its winning numbers held as a bitset (all
+/// numbers are below 100) and its match count computed up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: usize,
+    winning: u128,
+    held: Vec<u8>,
+    matches: usize,
+}
+
+impl Scratchcard {
+    pub fn parse_new(line: &str, line_number: usize) -> Result<Scratchcard, ParseError> {
+        let number = try_map(integer::<u32>, |number| {
+            if number < MAX_NUMBER {
+                Ok(number as u8)
+            } else {
+                Err(format!("number {number} is not below {MAX_NUMBER}"))
+            }
+        });
+        let input = Input::at_line(line, line_number);
+        let (id, input) = labelled("Card", integer::<usize>)(input)?;
+        let (_, input) = tag(":")(input)?;
+        let (winning_numbers, input) = list(&number)(input)?;
+        let (_, input) = tag("|")(input)?;
+        let (held, input) = list(&number)(input)?;
+        end(input)?;
+
+        let winning = winning_numbers.iter().fold(0u128, |winning, number| winning | 1 << number);
+        let matches = held.iter().filter(|number| winning & (1 << **number) != 0).count();
+        Ok(Scratchcard { id, winning, held, matches })
+    }
+
+    pub fn is_winning(&self, number: u8) -> bool {
+        number < MAX_NUMBER as u8 && self.winning & (1 << number) != 0
+    }
+
+    pub fn held(&self) -> &[u8] {
+        &self.held
+    }
+
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+}
+
+/// Parses every card, requiring the ids to run 1, 2, 3... in order since
+/// part two relies on a card's position to find the cards it wins.
+pub fn parse_cards(input: &str) -> Result<Vec<Scratchcard>, ParseError> {
+    input.lines().enumerate()
+    .map(|(index, line)| parse_numbered_card(line, index + 1))
+    .collect()
+}
+
+/// Parses each card as its line is read, with the same checks as
+/// `parse_cards`.
+pub fn read_cards(input: impl BufRead) -> impl Iterator<Item = Result<Scratchcard, Box<dyn Error>>> {
+    InputLines::new(input).enumerate()
+    .map(|(index, line)| Ok(parse_numbered_card(&line?, index + 1)?))
+}
+
+fn parse_numbered_card(line: &str, line_number: usize) -> Result<Scratchcard, ParseError> {
+    let card = Scratchcard::parse_new(line, line_number)?;
+    if card.id != line_number {
+        return Err(ParseError::new(line_number, 1, format!("expected card {line_number}, found card {}", card.id)));
+    }
+    Ok(card)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    #[test]
+    fn it_counts_matching_numbers() {
+        let card = Scratchcard::parse_new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 1).unwrap();
+        assert_eq!(card.matches(), 4);
+        assert!(card.is_winning(41));
+        assert!(!card.is_winning(6));
+        assert_eq!(card.held().len(), 8);
+    }
+
+    #[test]
+    fn it_rejects_numbers_out_of_range() {
+        let result = Scratchcard::parse_new("Card 1: 41 100 | 1", 4);
+        assert_eq!(result, Err(ParseError::new(4, 12, "number 100 is not below 100")));
+    }
+
+    #[test]
+    fn it_rejects_out_of_order_cards() {
+        let result = parse_cards("Card 1: 1 | 1\nCard 3: 2 | 2");
+        assert_eq!(result, Err(ParseError::new(2, 1, "expected card 2, found card 3")));
+    }
+
+    proptest! {
+        #[test]
+        fn bitset_agrees_with_the_listed_numbers(
+            winning in prop::collection::vec(0u8..100, 0..15),
+            held in prop::collection::vec(0u8..100, 0..30),
+        ) {
+            let join = |numbers: &[u8]| numbers.iter().map(u8::to_string).collect::<Vec<_>>().join(" ");
+            let card = Scratchcard::parse_new(&format!("Card 1: {} | {}", join(&winning), join(&held)), 1).unwrap();
+            for number in 0..=u8::MAX {
+                prop_assert_eq!(card.is_winning(number), winning.contains(&number));
+            }
+            prop_assert_eq!(card.held(), &held[..]);
+            prop_assert_eq!(card.matches(), held.iter().filter(|number| winning.contains(number)).count());
+            prop_assert!(card.matches() <= held.len());
+        }
+    }
+}
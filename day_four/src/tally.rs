@@ -0,0 +1,44 @@
+use std::fmt::{Debug, Display};
+
+/// An unsigned integer type that card totals can be counted in, so the
+/// width can be chosen to fit the input.
+pub trait Tally: Copy + Ord + Debug + Display {
+    const ZERO: Self;
+    const ONE: Self;
+    const NAME: &'static str;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn saturating_mul(self, other: Self) -> Self;
+    fn checked_pow2(exponent: usize) -> Option<Self>;
+    fn from_usize(value: usize) -> Option<Self>;
+}
+
+macro_rules! impl_tally {
+    ($($type:ty),*) => {
+        $(
+            impl Tally for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const NAME: &'static str = stringify!($type);
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$type>::checked_add(self, other)
+                }
+
+                fn saturating_mul(self, other: Self) -> Self {
+                    <$type>::saturating_mul(self, other)
+                }
+
+                fn checked_pow2(exponent: usize) -> Option<Self> {
+                    (2 as $type).checked_pow(exponent.try_into().ok()?)
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    value.try_into().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_tally!(u32, u64, u128);
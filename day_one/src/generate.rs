@@ -0,0 +1,25 @@
+use common::Rng;
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// `size` calibration lines of letters, digits and number words. Every line
+/// has at least one digit so both parts can be solved.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut result = String::new();
+    for _ in 0..size {
+        let mut tokens = vec![rng.between(1, 9).to_string()];
+        for _ in 0..rng.between(1, 8) {
+            let token = match rng.below(4) {
+                0 => rng.between(1, 9).to_string(),
+                1 => rng.choose(&WORDS).to_string(),
+                _ => (0..rng.between(1, 5)).map(|_| (b'a' + rng.below(26) as u8) as char).collect(),
+            };
+            tokens.push(token);
+        }
+        rng.shuffle(&mut tokens);
+        result.push_str(&tokens.concat());
+        result.push('\n');
+    }
+    result
+}
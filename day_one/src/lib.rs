@@ -1,71 +1,153 @@
-use std::collections::HashMap;
+use std::{error::Error, fmt::{self, Display}, io::BufRead};
 
+use common::{normalise, InputLines, ParseError};
 use lazy_static::lazy_static;
-use regex::Regex;
+
+mod generate;
+pub mod reference;
+mod vocabulary;
+
+pub use generate::generate;
+pub use vocabulary::Vocabulary;
 
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
+    }
+
+    fn streaming(&self) -> Option<&dyn common::StreamingSolution> {
+        Some(self)
     }
 }
 
-fn part_one(input: &str) -> u32 {
-    input.lines()
-    .map(|line| {
-        let first_digit = line.chars().find(char::is_ascii_digit).unwrap();
-        let last_digit = line.chars().rev().find(char::is_ascii_digit).unwrap();
-        let number = format!("{first_digit}{last_digit}");
-        number.parse::<u32>().unwrap()
-    })
-    .sum()
+impl common::StreamingSolution for Solution {
+    fn part_one(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        Ok(calibrate_stream(input, &DIGITS)?.into())
+    }
+
+    fn part_two(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        Ok(calibrate_stream(input, &ENGLISH)?.into())
+    }
 }
 
-lazy_static! {
-    static ref LEADING_NUMBER: Regex = Regex::new(r"^(one|two|three|four|five|six|seven|eight|nine)").unwrap();
-    static ref NUMBER_MAP: HashMap<&'static str, u32> = HashMap::from([
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9)
-    ]);
-}
-
-fn extract_numbers(input: &str) -> Vec<u32> {
-    let mut result: Vec<u32> = Vec::new();
-    for index in 0..input.len() {
-        let remainder = &input[index..];
-        let first_char = remainder.chars().next().unwrap();
-        if first_char.is_ascii_digit() {
-            result.push(first_char.to_digit(10).unwrap());
-            continue;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineError {
+    NonAscii { column: usize, character: char },
+    NoValue,
+    Overflow,
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineError::NonAscii { character, .. } => write!(f, "unexpected non-ASCII character {character:?}"),
+            LineError::NoValue => write!(f, "no calibration value found"),
+            LineError::Overflow => write!(f, "calibration value overflows u32"),
         }
-        let Some(string_number) = LEADING_NUMBER.captures(remainder) else {
-            continue;
-        };
-        let number = NUMBER_MAP.get(&string_number[0]).unwrap();
-        result.push(*number);
-    } 
-    result
-}
-
-fn part_two(input: &str) -> u32 {
-    input.lines()
-    .map(|line| {
-        let numbers = extract_numbers(line);
-        numbers.first().unwrap() * 10 + numbers.last().unwrap()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineCalibration {
+    pub line: usize,
+    pub result: Result<u32, LineError>,
+}
+
+impl LineCalibration {
+    fn to_parse_error(&self) -> Option<ParseError> {
+        match &self.result {
+            Ok(_) => None,
+            Err(error @ LineError::NonAscii { column, .. }) => Some(ParseError::new(self.line, *column, error.to_string())),
+            Err(error) => Some(ParseError::new(self.line, 1, error.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    pub total: u64,
+    pub skipped: Vec<LineCalibration>,
+}
+
+/// The calibration value of every line, being the digits of the first and
+/// last token's values written one after the other, so `ten` then `5` gives
+/// 105. Lines with non-ASCII text are rejected when the vocabulary has none.
+pub fn calibrate_lines(input: &str, vocabulary: &Vocabulary) -> Vec<LineCalibration> {
+    let check_ascii = vocabulary.is_ascii();
+    input.lines().enumerate()
+    .map(|(index, line)| {
+        let result = calibrate_line(line, vocabulary, check_ascii);
+        LineCalibration { line: index + 1, result }
     })
-    .sum()
+    .collect()
+}
+
+fn calibrate_line(line: &str, vocabulary: &Vocabulary, check_ascii: bool) -> Result<u32, LineError> {
+    if check_ascii {
+        if let Some((column, character)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(LineError::NonAscii { column: column + 1, character });
+        }
+    }
+    let first = vocabulary.first_value(line).ok_or(LineError::NoValue)?;
+    let last = vocabulary.last_value(line).ok_or(LineError::NoValue)?;
+    let last_digits = last.checked_ilog10().unwrap_or(0) + 1;
+    10u32.checked_pow(last_digits)
+        .and_then(|shift| first.checked_mul(shift))
+        .and_then(|value| value.checked_add(last))
+        .ok_or(LineError::Overflow)
+}
+
+/// Sums the calibration values, failing on the first line without one.
+pub fn calibrate(input: &str, vocabulary: &Vocabulary) -> Result<u64, ParseError> {
+    calibrate_lines(input, vocabulary).into_iter().try_fold(0, add_calibration)
+}
+
+/// Sums the calibration values as `calibrate` does, reading one line at a
+/// time.
+pub fn calibrate_stream(input: impl BufRead, vocabulary: &Vocabulary) -> Result<u64, Box<dyn Error>> {
+    let check_ascii = vocabulary.is_ascii();
+    let mut total = 0;
+    for (index, line) in InputLines::new(input).enumerate() {
+        let line = LineCalibration { line: index + 1, result: calibrate_line(&line?, vocabulary, check_ascii) };
+        total = add_calibration(total, line)?;
+    }
+    Ok(total)
+}
+
+fn add_calibration(total: u64, line: LineCalibration) -> Result<u64, ParseError> {
+    if let Some(error) = line.to_parse_error() {
+        return Err(error);
+    }
+    total.checked_add(line.result.unwrap().into())
+        .ok_or_else(|| ParseError::new(line.line, 1, "total calibration value overflows u64"))
+}
+
+/// Sums the calibration values, skipping lines without one and returning
+/// them alongside the total.
+pub fn calibrate_lenient(input: &str, vocabulary: &Vocabulary) -> Calibration {
+    let (valid, skipped): (Vec<_>, Vec<_>) = calibrate_lines(input, vocabulary).into_iter()
+        .partition(|line| line.result.is_ok());
+    let total = valid.iter().map(|line| u64::from(*line.result.as_ref().unwrap())).sum();
+    Calibration { total, skipped }
+}
+
+lazy_static! {
+    static ref DIGITS: Vocabulary = Vocabulary::digits();
+    static ref ENGLISH: Vocabulary = Vocabulary::english();
+}
+
+fn part_one(input: &str) -> Result<u64, ParseError> {
+    calibrate(&normalise(input), &DIGITS)
+}
+
+fn part_two(input: &str) -> Result<u64, ParseError> {
+    calibrate(&normalise(input), &ENGLISH)
 }
 
 #[cfg(test)]
@@ -73,6 +155,10 @@ mod tests {
     use super::*;
     use indoc::indoc;
 
+    fn extract_numbers(input: &str) -> Vec<u32> {
+        ENGLISH.values(input)
+    }
+
     fn part1_input() -> &'static str {
         indoc! {"1abc2
                 pqr3stu8vwx
@@ -83,7 +169,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(part1_input());
-        assert_eq!(result, 142);
+        assert_eq!(result, Ok(142));
     }
 
     #[test]
@@ -105,6 +191,80 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(part2_input());
-        assert_eq!(result, 281);
+        assert_eq!(result, Ok(281));
+    }
+
+    #[test]
+    fn it_calibrates_a_stream_of_lines() {
+        let input = format!("\u{feff}{}\r\n\r\n", part2_input().replace('\n', " \r\n"));
+        assert_eq!(calibrate_stream(input.as_bytes(), &ENGLISH).unwrap(), 281);
+        let error = calibrate_stream("1abc2\n\npqr3\n".as_bytes(), &DIGITS).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: no calibration value found");
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", part1_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(142));
+        let input = format!("\u{feff}{}\r\n\r\n", part2_input().replace('\n', " \r\n"));
+        assert_eq!(part_two(&input), Ok(281));
+    }
+
+    #[test]
+    fn it_skips_multi_byte_characters_when_extracting() {
+        let result = extract_numbers("twoé1ñine");
+        assert_eq!(result, vec![2, 1]);
+    }
+
+    #[test]
+    fn it_extracts_overlapping_words() {
+        let result = extract_numbers("eightwone");
+        assert_eq!(result, vec![8, 2, 1]);
+        let result = part_two("eightwo\noneight");
+        assert_eq!(result, Ok(82 + 18));
+    }
+
+    #[test]
+    fn it_calibrates_with_custom_vocabularies() {
+        let vocabulary = Vocabulary::parse_new("zero 0\nten 10\nfünf 5").unwrap();
+        let result = calibrate("tenxfünf\nzero", &vocabulary);
+        assert_eq!(result, Ok(105));
+        let result = calibrate("ten\nfünften", &vocabulary);
+        assert_eq!(result, Ok(1010 + 510));
+    }
+
+    #[test]
+    fn it_reports_calibration_values_that_overflow() {
+        let vocabulary = Vocabulary::parse_new("a 4294967295\nb 30000").unwrap();
+        assert_eq!(calibrate("a", &vocabulary), Err(ParseError::new(1, 1, "calibration value overflows u32")));
+        assert_eq!(calibrate("bb\nbb", &vocabulary), Ok(6_000_060_000));
+        assert_eq!(calibrate_stream("bb\nbb".as_bytes(), &vocabulary).unwrap(), 6_000_060_000);
+        let result = calibrate_lenient("a\nbb\nbb", &vocabulary);
+        assert_eq!((result.total, result.skipped.len()), (6_000_060_000, 1));
+    }
+
+    #[test]
+    fn it_reports_the_line_without_digits() {
+        let result = part_one("1abc2\nabc\n3");
+        assert_eq!(result, Err(ParseError::new(2, 1, "no calibration value found")));
+    }
+
+    #[test]
+    fn it_skips_bad_lines_when_lenient() {
+        let result = calibrate_lenient("1abc2\nabc\né3\n3", &Vocabulary::digits());
+        assert_eq!(result.total, 12 + 33);
+        let skipped: Vec<_> = result.skipped.iter().map(|line| (line.line, line.result.clone())).collect();
+        assert_eq!(skipped, vec![
+            (2, Err(LineError::NoValue)),
+            (3, Err(LineError::NonAscii { column: 1, character: 'é' })),
+        ]);
+    }
+
+    #[test]
+    fn it_rejects_non_ascii_calibration_lines() {
+        let result = part_two("two1nine\ntwoé1nine");
+        assert_eq!(result, Err(ParseError::new(2, 4, "unexpected non-ASCII character 'é'")));
+        let result = part_one("1abc2\n€3");
+        assert_eq!(result, Err(ParseError::new(2, 1, "unexpected non-ASCII character '€'")));
     }
 }
@@ -0,0 +1,31 @@
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Reads every digit of every line and keeps the first and last.
+pub fn part_one(input: &str) -> u64 {
+    input.lines()
+    .map(|line| {
+        let digits: Vec<_> = line.chars().filter_map(|c| c.to_digit(10)).collect();
+        u64::from(digits[0] * 10 + digits[digits.len() - 1])
+    })
+    .sum()
+}
+
+/// Checks every word at every character offset of every line.
+pub fn part_two(input: &str) -> u64 {
+    input.lines()
+    .map(|line| {
+        let mut numbers = Vec::new();
+        for (index, character) in line.char_indices() {
+            if let Some(digit) = character.to_digit(10) {
+                numbers.push(digit);
+            }
+            for (value, word) in WORDS.iter().enumerate() {
+                if line[index..].starts_with(word) {
+                    numbers.push(value as u32 + 1);
+                }
+            }
+        }
+        u64::from(numbers[0] * 10 + numbers[numbers.len() - 1])
+    })
+    .sum()
+}
@@ -0,0 +1,138 @@
+use common::{column_of, ParseError, WordScanner};
+
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0), ("1", 1), ("2", 2), ("3", 3), ("4", 4),
+    ("5", 5), ("6", 6), ("7", 7), ("8", 8), ("9", 9),
+];
+
+const ENGLISH_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9)
+];
+
+/// The set of tokens recognised in a calibration line and the value each
+/// one stands for.
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+    scanner: WordScanner,
+}
+
+impl Vocabulary {
+    pub fn new(words: Vec<(String, u32)>) -> Vocabulary {
+        let scanner = WordScanner::new(&words.iter().map(|(word, _)| word.as_str()).collect::<Vec<_>>());
+        Vocabulary { words, scanner }
+    }
+
+    pub fn digits() -> Vocabulary {
+        Vocabulary::from_pairs(DIGITS.iter())
+    }
+
+    pub fn english() -> Vocabulary {
+        Vocabulary::from_pairs(DIGITS.iter().chain(ENGLISH_WORDS.iter()))
+    }
+
+    fn from_pairs<'a>(pairs: impl Iterator<Item = &'a (&'a str, u32)>) -> Vocabulary {
+        Vocabulary::new(pairs.map(|(word, value)| (word.to_string(), *value)).collect())
+    }
+
+    /// Reads one `word value` pair per line. Blank lines and lines starting
+    /// with `#` are ignored.
+    pub fn parse_new(input: &str) -> Result<Vocabulary, ParseError> {
+        let mut words = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let error = |part: &str, message: String| ParseError::new(index + 1, column_of(line, part), message);
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let mut parts = content.split_whitespace();
+            let word = parts.next().unwrap();
+            let Some(value_text) = parts.next() else {
+                return Err(error(word, format!("missing value for {word:?}")));
+            };
+            let value = value_text.parse::<u32>()
+                .map_err(|_| error(value_text, format!("invalid value {value_text:?} for {word:?}")))?;
+            if let Some(extra) = parts.next() {
+                return Err(error(extra, "expected a word followed by a single value".to_string()));
+            }
+            words.push((word.to_string(), value));
+        }
+        Ok(Vocabulary::new(words))
+    }
+
+    pub fn is_ascii(&self) -> bool {
+        self.words.iter().all(|(word, _)| word.is_ascii())
+    }
+
+    pub fn first_value(&self, line: &str) -> Option<u32> {
+        self.scanner.first_match(line).map(|word_match| self.words[word_match.pattern].1)
+    }
+
+    pub fn last_value(&self, line: &str) -> Option<u32> {
+        self.scanner.last_match(line).map(|word_match| self.words[word_match.pattern].1)
+    }
+
+    pub fn values(&self, line: &str) -> Vec<u32> {
+        self.scanner.find_overlapping(line).iter()
+        .map(|word_match| self.words[word_match.pattern].1)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    #[test]
+    fn it_parses_a_vocabulary_file() {
+        let vocabulary = Vocabulary::parse_new(indoc! {"
+            # German
+            eins 1
+            zwei 2
+            drei 3
+            zehn 10
+            "}).unwrap();
+        assert_eq!(vocabulary.values("zweinsxzehn"), vec![2, 1, 10]);
+    }
+
+    #[test]
+    fn it_reports_the_line_of_a_bad_entry() {
+        let result = Vocabulary::parse_new("one 1\ntwo two");
+        assert_eq!(result.err(), Some(ParseError::new(2, 5, "invalid value \"two\" for \"two\"")));
+        let result = Vocabulary::parse_new("one 1\n  zwei");
+        assert_eq!(result.err(), Some(ParseError::new(2, 3, "missing value for \"zwei\"")));
+        let result = Vocabulary::parse_new("drei 3 4");
+        assert_eq!(result.err(), Some(ParseError::new(1, 8, "expected a word followed by a single value")));
+    }
+
+    fn token() -> impl Strategy<Value = (&'static str, u32)> {
+        prop::sample::select(DIGITS.iter().chain(ENGLISH_WORDS.iter()).copied().collect::<Vec<_>>())
+    }
+
+    /// Letters that appear in none of the number words.
+    fn noise() -> impl Strategy<Value = String> {
+        "[abcdjklmpqy]{1,4}"
+    }
+
+    proptest! {
+        #[test]
+        fn noise_between_tokens_does_not_change_the_values(tokens in prop::collection::vec((token(), noise()), 1..8)) {
+            let line: String = tokens.iter().map(|((word, _), noise)| format!("{word}{noise}")).collect();
+            let expected: Vec<_> = tokens.iter().map(|((_, value), _)| *value).collect();
+            let vocabulary = Vocabulary::english();
+            prop_assert_eq!(vocabulary.values(&line), expected.clone());
+            prop_assert_eq!(vocabulary.first_value(&line), expected.first().copied());
+            prop_assert_eq!(vocabulary.last_value(&line), expected.last().copied());
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::{error::Error, fmt::{self, Display}};
+
+use common::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinningsError {
+    Parse(ParseError),
+    Overflow,
+}
+
+impl Display for WinningsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinningsError::Parse(error) => write!(f, "{error}"),
+            WinningsError::Overflow => write!(f, "total winnings overflow u64"),
+        }
+    }
+}
+
+impl Error for WinningsError {}
+
+impl From<ParseError> for WinningsError {
+    fn from(error: ParseError) -> WinningsError {
+        WinningsError::Parse(error)
+    }
+}
@@ -0,0 +1,21 @@
+use std::collections::HashSet;
+
+use common::Rng;
+
+const CARDS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+const MAX_HANDS: usize = 13 * 13 * 13 * 13 * 13;
+
+/// `size` distinct random hands of five cards with bids up to 1000. Hands
+/// never repeat, as in the puzzle, so every ranking is unambiguous.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut seen = HashSet::new();
+    let mut result = String::new();
+    while seen.len() < size.min(MAX_HANDS) {
+        let hand: String = (0..5).map(|_| *rng.choose(&CARDS)).collect();
+        if seen.insert(hand.clone()) {
+            result.push_str(&format!("{hand} {}\n", rng.between(1, 1000)));
+        }
+    }
+    result
+}
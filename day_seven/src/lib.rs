@@ -1,46 +1,75 @@
-use std::{collections::HashMap, cmp::Ordering, iter::zip};
+use std::{collections::HashMap, cmp::Ordering, error::Error, io::BufRead, iter::zip};
+
+use common::{column_of, normalise, InputLines, ParseError};
+
+mod error;
+mod generate;
+pub mod reference;
+
+pub use error::WinningsError;
+pub use generate::generate;
 
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
+    }
+
+    fn streaming(&self) -> Option<&dyn common::StreamingSolution> {
+        Some(self)
+    }
+}
+
+impl common::StreamingSolution for Solution {
+    fn part_one(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        Ok(total_winnings_stream(input, false)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_two(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        Ok(total_winnings_stream(input, true)?.into())
     }
 }
 
-struct Hand<'a> {
+const CARDS: &str = "23456789TJQKA";
+
+pub struct Hand<'a> {
     cards: &'a str,
     bid: u64,
     strength: u64,
 }
 
 impl<'a> Hand<'a> {
-    fn parse_new(input: &'a str) -> Hand<'a> {
-        let mut parts = input.split_whitespace();
-        let cards = parts.next().unwrap();
-        let bid = parts.next().unwrap().parse::<u64>().unwrap();
-        let strength = Hand::calc_hand_strength(cards);
-        Hand {
-            cards,
-            bid,
-            strength
-        }
+    fn parse_new(input: &'a str, line_number: usize) -> Result<Hand<'a>, ParseError> {
+        Hand::parse_with(input, line_number, Hand::calc_hand_strength)
     }
 
-    fn parse_part_two(input: &'a str) -> Hand<'a> {
+    fn parse_part_two(input: &'a str, line_number: usize) -> Result<Hand<'a>, ParseError> {
+        Hand::parse_with(input, line_number, Hand::calc_part_two_hand_strength)
+    }
+
+    fn parse_with(input: &'a str, line_number: usize, strength: fn(&str) -> u64) -> Result<Hand<'a>, ParseError> {
+        let error = |part: &str, message: String| {
+            ParseError::new(line_number, column_of(input, part), message)
+        };
         let mut parts = input.split_whitespace();
-        let cards = parts.next().unwrap();
-        let bid = parts.next().unwrap().parse::<u64>().unwrap();
-        let strength = Hand::calc_part_two_hand_strength(cards);
-        Hand {
+        let (Some(cards), Some(bid), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(error(input, "expected '<cards> <bid>'".to_string()));
+        };
+        if cards.len() != 5 || !cards.chars().all(|card| CARDS.contains(card)) {
+            return Err(error(cards, format!("expected five cards from {CARDS}, found {cards:?}")));
+        }
+        let bid = bid.parse::<u64>()
+            .map_err(|_| error(bid, format!("invalid bid {bid:?}")))?;
+        Ok(Hand {
             cards,
             bid,
-            strength
-        }
+            strength: strength(cards),
+        })
     }
 
     fn calc_hand_strength(input: &str) -> u64 {
@@ -78,7 +107,7 @@ impl<'a> Hand<'a> {
         let joker_count = card_map.remove(&'J').unwrap_or(0);
         let max_key = card_map
             .iter()
-            .max_by(|a, b| a.1.cmp(&b.1))
+            .max_by(|a, b| a.1.cmp(b.1))
             .map(|(k, _v)| k);
         match max_key {
             Some(key) => {*card_map.entry(*key).or_default() += joker_count;},
@@ -121,48 +150,104 @@ impl<'a> Hand<'a> {
     }
 }
 
-fn part_one(input: &str) -> u64 {
+/// Parses one hand per line, ranking jokers as the weakest wildcard when
+/// `jokers` is set.
+pub fn parse_hands(input: &str, jokers: bool) -> Result<Vec<Hand<'_>>, ParseError> {
+    let parse = if jokers {
+        Hand::parse_part_two
+    } else {
+        Hand::parse_new
+    };
+    input.lines().enumerate()
+    .map(|(index, line)| parse(line, index + 1))
+    .collect()
+}
+
+fn part_one(input: &str) -> Result<u64, WinningsError> {
     let card_map = create_card_map(&[
         '2','3','4','5','6','7','8','9','T','J','Q','K','A'
     ]);
-    let mut hands: Vec<_> = input.lines()
-    .map(Hand::parse_new)
-    .collect();
-    hands.sort_by(|l_hand, r_hand| {
-        if l_hand.strength > r_hand.strength {
-            Ordering::Greater
-        } else if l_hand.strength < r_hand.strength {
-            Ordering::Less
-        } else {
-            l_hand.order_by_sub_strength(r_hand, &card_map)
-        }
-    });
-    hands.iter().enumerate()
-    .fold(0, |acc, (index, hand)| {
-        acc + (index as u64 + 1) * hand.bid
-    })
+    let input = normalise(input);
+    let mut hands = parse_hands(&input, false)?;
+    hands.sort_by(|l_hand, r_hand| compare_hands(l_hand, r_hand, &card_map));
+    total_winnings(&hands)
 }
 
-fn part_two(input: &str) -> u64 {
+fn part_two(input: &str) -> Result<u64, WinningsError> {
     let card_map = create_card_map(&[
         'J','2','3','4','5','6','7','8','9','T','Q','K','A'
     ]);
-    let mut hands: Vec<_> = input.lines()
-    .map(Hand::parse_part_two)
-    .collect();
-    hands.sort_by(|l_hand, r_hand| {
-        if l_hand.strength > r_hand.strength {
-            Ordering::Greater
-        } else if l_hand.strength < r_hand.strength {
-            Ordering::Less
-        } else {
-            l_hand.order_by_sub_strength(r_hand, &card_map)
-        }
-    });
-    hands.iter().enumerate()
-    .fold(0, |acc, (index, hand)| {
-        acc + (index as u64 + 1) * hand.bid
-    })
+    let input = normalise(input);
+    let mut hands = parse_hands(&input, true)?;
+    hands.sort_by(|l_hand, r_hand| compare_hands(l_hand, r_hand, &card_map));
+    total_winnings(&hands)
+}
+
+/// Each bid times its hand's rank, for hands already sorted weakest first.
+fn total_winnings(hands: &[Hand]) -> Result<u64, WinningsError> {
+    let mut total = 0u64;
+    for (index, hand) in hands.iter().enumerate() {
+        total = (index as u64 + 1).checked_mul(hand.bid)
+            .and_then(|winnings| total.checked_add(winnings))
+            .ok_or(WinningsError::Overflow)?;
+    }
+    Ok(total)
+}
+
+/// Hands with the same cards, tallied so their winnings can be added up
+/// once every hand has been read.
+#[derive(Debug, Clone, Copy, Default)]
+struct HandBucket {
+    count: u64,
+    bids: u64,
+    /// Each bid times the number of hands with these cards read before it.
+    bids_by_arrival: u64,
+    strength: u64,
+}
+
+/// Total winnings as the parts work them out, reading one hand at a time.
+/// Hands are tallied by their cards, numbered so that the numbers run in the
+/// same order as comparing the cards one by one, so memory stays at one
+/// bucket per distinct hand read, and never more than one per possible
+/// hand however long the input is. Hands with the same cards rank in the
+/// order they were read, as with the stable sort.
+pub fn total_winnings_stream(input: impl BufRead, jokers: bool) -> Result<u64, Box<dyn Error>> {
+    let (order, strength): (&str, fn(&str) -> u64) = if jokers {
+        ("J23456789TQKA", Hand::calc_part_two_hand_strength)
+    } else {
+        (CARDS, Hand::calc_hand_strength)
+    };
+    let card_map = create_card_map(&order.chars().collect::<Vec<_>>());
+    let mut buckets: HashMap<usize, HandBucket> = HashMap::new();
+    for (index, line) in InputLines::new(input).enumerate() {
+        let line = line?;
+        let hand = Hand::parse_with(&line, index + 1, strength)?;
+        let position = hand.cards.chars().fold(0, |position, card| position * order.len() + card_map[&card] as usize);
+        let bucket = buckets.entry(position).or_default();
+        bucket.bids_by_arrival = bucket.count.checked_mul(hand.bid)
+            .and_then(|winnings| bucket.bids_by_arrival.checked_add(winnings))
+            .ok_or(WinningsError::Overflow)?;
+        bucket.count += 1;
+        bucket.bids = bucket.bids.checked_add(hand.bid).ok_or(WinningsError::Overflow)?;
+        bucket.strength = hand.strength;
+    }
+    let mut buckets: Vec<_> = buckets.into_iter().collect();
+    buckets.sort_unstable_by_key(|(position, bucket)| (bucket.strength, *position));
+    let mut ranked = 0u64;
+    let mut total = 0u64;
+    for (_, bucket) in buckets {
+        total = (ranked + 1).checked_mul(bucket.bids)
+            .and_then(|winnings| winnings.checked_add(bucket.bids_by_arrival))
+            .and_then(|winnings| total.checked_add(winnings))
+            .ok_or(WinningsError::Overflow)?;
+        ranked += bucket.count;
+    }
+    Ok(total)
+}
+
+fn compare_hands(l_hand: &Hand, r_hand: &Hand, card_map: &HashMap<char, u8>) -> Ordering {
+    l_hand.strength.cmp(&r_hand.strength)
+        .then_with(|| l_hand.order_by_sub_strength(r_hand, card_map))
 }
 
 fn create_card_map(char_order: &[char]) -> HashMap<char, u8> {
@@ -177,6 +262,7 @@ fn create_card_map(char_order: &[char]) -> HashMap<char, u8> {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
 
     fn test_input() -> &'static str {
         indoc! {"32T3K 765
@@ -189,12 +275,97 @@ mod tests {
     #[test]
     fn it_multiplies_bid_by_rank() {
         let result = part_one(test_input());
-        assert_eq!(result, 6440);
+        assert_eq!(result, Ok(6440));
     }
 
     #[test]
     fn it_multiplies_bid_by_rank_using_jokers() {
         let result = part_two(test_input());
-        assert_eq!(result, 5905);
+        assert_eq!(result, Ok(5905));
+    }
+
+    #[test]
+    fn it_ranks_a_stream_of_hands() {
+        let input = format!("{}\nKK677 3\n32T3K 41\n", test_input());
+        for jokers in [false, true] {
+            let expected = if jokers { part_two(&input) } else { part_one(&input) };
+            assert_eq!(total_winnings_stream(input.as_bytes(), jokers).ok(), expected.ok());
+            let input = generate(500, 3);
+            let expected = if jokers { part_two(&input) } else { part_one(&input) };
+            assert_eq!(total_winnings_stream(input.as_bytes(), jokers).ok(), expected.ok());
+        }
+        let result = total_winnings_stream("32T3K 765\n32T3 684".as_bytes(), false).map_err(|error| error.to_string());
+        assert_eq!(result, Err("line 2, column 1: expected five cards from 23456789TJQKA, found \"32T3\"".to_string()));
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", test_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(6440));
+        assert_eq!(part_two(&input), Ok(5905));
+    }
+
+    #[test]
+    fn it_reports_malformed_hands() {
+        let result = part_one("32T3K 765\n32T3 765");
+        assert_eq!(result, Err(ParseError::new(2, 1, "expected five cards from 23456789TJQKA, found \"32T3\"").into()));
+        let result = part_two("32T3K 765\n32T3K");
+        assert_eq!(result, Err(ParseError::new(2, 1, "expected '<cards> <bid>'").into()));
+        let result = part_two("32T3K -765");
+        assert_eq!(result, Err(ParseError::new(1, 7, "invalid bid \"-765\"").into()));
+    }
+
+    #[test]
+    fn it_reports_winnings_that_overflow() {
+        let input = "AAAAA 18446744073709551615\nKKKKK 2";
+        assert_eq!(part_one(input), Err(WinningsError::Overflow));
+        assert_eq!(part_two(input), Err(WinningsError::Overflow));
+        for jokers in [false, true] {
+            let result = total_winnings_stream(input.as_bytes(), jokers).map_err(|error| error.to_string());
+            assert_eq!(result, Err(WinningsError::Overflow.to_string()));
+        }
+        let input = "KKKKK 18446744073709551615\nKKKKK 1";
+        let result = total_winnings_stream(input.as_bytes(), false).map_err(|error| error.to_string());
+        assert_eq!(result, Err(WinningsError::Overflow.to_string()));
+        assert_eq!(part_one("AAAAA 9223372036854775807\nKKKKK 0"), Ok(u64::MAX - 1));
+    }
+
+    fn cards() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(CARDS.chars().collect::<Vec<_>>()), 5).prop_map(String::from_iter)
+    }
+
+    fn hand(cards: &str, jokers: bool) -> Hand<'_> {
+        let strength = if jokers {
+            Hand::calc_part_two_hand_strength(cards)
+        } else {
+            Hand::calc_hand_strength(cards)
+        };
+        Hand { cards, bid: 0, strength }
+    }
+
+    proptest! {
+        #[test]
+        fn hands_are_totally_ordered(a in cards(), b in cards(), c in cards(), jokers in any::<bool>()) {
+            let card_map = if jokers {
+                create_card_map(&['J','2','3','4','5','6','7','8','9','T','Q','K','A'])
+            } else {
+                create_card_map(&CARDS.chars().collect::<Vec<_>>())
+            };
+            let (a, b, c) = (hand(&a, jokers), hand(&b, jokers), hand(&c, jokers));
+            let compare = |l: &Hand, r: &Hand| compare_hands(l, r, &card_map);
+            prop_assert_eq!(compare(&a, &b), compare(&b, &a).reverse());
+            prop_assert_eq!(compare(&a, &b) == Ordering::Equal, a.cards == b.cards);
+            if compare(&a, &b).is_le() && compare(&b, &c).is_le() {
+                prop_assert!(compare(&a, &c).is_le());
+            }
+            if a.strength > b.strength {
+                prop_assert_eq!(compare(&a, &b), Ordering::Greater);
+            }
+        }
+
+        #[test]
+        fn jokers_never_weaken_a_hand(cards in cards()) {
+            prop_assert!(Hand::calc_part_two_hand_strength(&cards) >= Hand::calc_hand_strength(&cards));
+        }
     }
 }
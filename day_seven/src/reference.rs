@@ -0,0 +1,52 @@
+const CARDS: &str = "23456789TJQKA";
+const JOKER_CARDS: &str = "J23456789TQKA";
+
+/// Ranks a hand by its sorted card counts, e.g. `[3, 2]` for a full house,
+/// which orders the hand types correctly when compared.
+fn hand_type(cards: &str) -> Vec<usize> {
+    let mut counts: Vec<_> = CARDS.chars()
+        .map(|card| cards.chars().filter(|c| *c == card).count())
+        .filter(|count| *count > 0)
+        .collect();
+    counts.sort_by(|a, b| b.cmp(a));
+    counts
+}
+
+/// Tries every card in place of every joker and keeps the best type.
+fn best_joker_type(cards: &str) -> Vec<usize> {
+    match cards.find('J') {
+        None => hand_type(cards),
+        Some(index) => CARDS.chars()
+            .filter(|card| *card != 'J')
+            .map(|card| {
+                let mut replaced = cards.to_string();
+                replaced.replace_range(index..index + 1, &card.to_string());
+                best_joker_type(&replaced)
+            })
+            .max()
+            .unwrap(),
+    }
+}
+
+fn winnings(input: &str, key: impl Fn(&str) -> (Vec<usize>, Vec<usize>)) -> u64 {
+    let mut hands: Vec<_> = input.lines()
+        .map(|line| {
+            let (cards, bid) = line.split_once(' ').unwrap();
+            (key(cards), bid.parse::<u64>().unwrap())
+        })
+        .collect();
+    hands.sort();
+    hands.iter().enumerate().map(|(index, (_, bid))| (index as u64 + 1) * bid).sum()
+}
+
+pub fn part_one(input: &str) -> u64 {
+    winnings(input, |cards| {
+        (hand_type(cards), cards.chars().map(|card| CARDS.find(card).unwrap()).collect())
+    })
+}
+
+pub fn part_two(input: &str) -> u64 {
+    winnings(input, |cards| {
+        (best_joker_type(cards), cards.chars().map(|card| JOKER_CARDS.find(card).unwrap()).collect())
+    })
+}
@@ -0,0 +1,19 @@
+use common::Rng;
+
+/// `size` races with two-digit times and records that can always be
+/// beaten. Answers only fit in a u64 for up to nine races in part one, and
+/// four in part two where every race's digits are joined together; past
+/// that the parts report the overflow as an error.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let races: Vec<_> = (0..size)
+    .map(|_| {
+        let time = rng.between(10, 99);
+        let best = (time / 2) * (time - time / 2);
+        (time, rng.between(time, best - 1))
+    })
+    .collect();
+    let times: Vec<_> = races.iter().map(|(time, _)| format!("{time:>4}")).collect();
+    let distances: Vec<_> = races.iter().map(|(_, distance)| format!("{distance:>4}")).collect();
+    format!("Time:    {}\nDistance:{}\n", times.join(" "), distances.join(" "))
+}
@@ -1,45 +1,76 @@
 use std::iter::zip;
 
+use common::{parser::{end, integer, labelled, line, list, token, Input, ParseResult}, normalise, ParseError};
+
+mod generate;
+pub mod reference;
+
+pub use generate::generate;
+
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
     }
 }
 
-fn part_one(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let times = extract_numbers(lines.next().unwrap());
-    let distances = extract_numbers(lines.next().unwrap());
-    zip(times, distances)
-    .map(|(time, distance)| {
-        count_winning_methods(&time, &distance)
-    })
-    .fold(1u64, |acc, x| acc * x)
+/// A race's time limit and the record distance to beat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+}
+
+/// Parses the `Time:` and `Distance:` lines as one race per column.
+pub fn parse_races(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (times, distances_line) = line(labelled("Time:", list(integer::<u64>)))(Input::new(input))?;
+    let (distances, rest) = line(labelled("Distance:", list(integer::<u64>)))(distances_line)?;
+    end(rest)?;
+    if times.len() != distances.len() {
+        return Err(distances_line.error(format!("expected {} distances, found {}", times.len(), distances.len())));
+    }
+    Ok(zip(times, distances).map(|(time, record)| Race { time, record }).collect())
 }
 
-fn extract_numbers(input: &str) -> Vec<u64> {
-    input
-    .split_whitespace()
-    .skip(1)
-    .map(str::parse::<u64>)
-    .map(Result::unwrap)
-    .collect()
+/// Parses the `Time:` and `Distance:` lines as a single race, ignoring the
+/// spaces between digits.
+pub fn parse_single_race(input: &str) -> Result<Race, ParseError> {
+    let (time, input) = line(labelled("Time:", joined_number))(Input::new(input))?;
+    let (record, input) = line(labelled("Distance:", joined_number))(input)?;
+    end(input)?;
+    Ok(Race { time, record })
 }
 
-fn extract_number(input: &str) -> u64 {
-    let combined = input
-    .split_whitespace()
-    .skip(1)
-    .fold(String::new(), |acc, num| {
-        acc + num
-    });
-    combined.parse::<u64>().unwrap()
+fn joined_number(input: Input<'_>) -> ParseResult<'_, u64> {
+    let (parts, rest) = list(token)(input)?;
+    let mut result = None::<u64>;
+    for part in parts {
+        if !part.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(input.error_at(part, format!("invalid number {part:?}")));
+        }
+        result = part.bytes().try_fold(result.unwrap_or(0), |acc, digit| {
+            acc.checked_mul(10)?.checked_add(u64::from(digit - b'0'))
+        })
+        .map(Some)
+        .ok_or_else(|| input.error_at(part, "number is too large"))?;
+    }
+    match result {
+        Some(number) => Ok((number, rest)),
+        None => Err(input.error("expected a number")),
+    }
+}
+
+fn part_one(input: &str) -> Result<u64, ParseError> {
+    parse_races(&normalise(input))?.iter()
+    .try_fold(1u64, |product, race| {
+        product.checked_mul(count_winning_methods(&race.time, &race.record))
+    })
+    .ok_or_else(|| ParseError::new(1, 1, "product of the ways to win overflows u64"))
 }
 
 fn count_winning_methods(time: &u64, distance: &u64) -> u64 {
@@ -63,17 +94,16 @@ fn calculate_distance(time: &u64, x: u64) -> u64 {
     time * x - x.pow(2)
 }
 
-fn part_two(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let time = extract_number(lines.next().unwrap());
-    let distance = extract_number(lines.next().unwrap());
-    count_winning_methods(&time, &distance)
+fn part_two(input: &str) -> Result<u64, ParseError> {
+    let race = parse_single_race(&normalise(input))?;
+    Ok(count_winning_methods(&race.time, &race.record))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
 
     fn test_input() -> &'static str {
         indoc! {"Time:      7  15   30
@@ -83,12 +113,49 @@ mod tests {
     #[test]
     fn it_multiplies_ways_of_beating_record() {
         let result = part_one(test_input());
-        assert_eq!(result, 288);
+        assert_eq!(result, Ok(288));
     }
 
     #[test]
     fn it_finds_combined_ways_to_beat_record() {
         let result = part_two(test_input());
-        assert_eq!(result, 71503);
+        assert_eq!(result, Ok(71503));
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", test_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(288));
+        assert_eq!(part_two(&input), Ok(71503));
+    }
+
+    #[test]
+    fn it_reports_mismatched_lines() {
+        let result = part_one("Time: 7 15\nDistance: 9");
+        assert_eq!(result, Err(ParseError::new(2, 1, "expected 2 distances, found 1")));
+        let result = part_two("Time: 7 1x5\nDistance: 9");
+        assert_eq!(result, Err(ParseError::new(1, 9, "invalid number \"1x5\"")));
+        let result = part_two("Time: 99999999999 99999999999\nDistance: 9");
+        assert_eq!(result, Err(ParseError::new(1, 19, "number is too large")));
+    }
+
+    #[test]
+    fn it_reports_products_that_overflow() {
+        let result = part_one(&crate::generate(100, 0));
+        assert_eq!(result, Err(ParseError::new(1, 1, "product of the ways to win overflows u64")));
+    }
+
+    proptest! {
+        #[test]
+        fn distance_is_symmetric_in_hold_time(time in 0u64..10_000, x in 0u64..10_000) {
+            let x = x.min(time);
+            prop_assert_eq!(calculate_distance(&time, x), calculate_distance(&time, time - x));
+        }
+
+        #[test]
+        fn winning_methods_match_a_brute_force_count(time in 0u64..300, distance in 0u64..25_000) {
+            let expected = (0..=time).filter(|x| calculate_distance(&time, *x) > distance).count() as u64;
+            prop_assert_eq!(count_winning_methods(&time, &distance), expected);
+        }
     }
 }
@@ -0,0 +1,24 @@
+fn numbers(line: &str) -> Vec<u64> {
+    line.split_whitespace().skip(1).map(|number| number.parse().unwrap()).collect()
+}
+
+fn joined_number(line: &str) -> u64 {
+    line.split_whitespace().skip(1).collect::<String>().parse().unwrap()
+}
+
+/// Tries every whole millisecond the button could be held for.
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    (0..=time).filter(|held| held * (time - held) > record).count() as u64
+}
+
+pub fn part_one(input: &str) -> u64 {
+    let lines: Vec<_> = input.lines().collect();
+    numbers(lines[0]).iter().zip(numbers(lines[1]))
+    .map(|(time, record)| ways_to_win(*time, record))
+    .product()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    let lines: Vec<_> = input.lines().collect();
+    ways_to_win(joined_number(lines[0]), joined_number(lines[1]))
+}
@@ -0,0 +1,79 @@
+use std::ops::RangeInclusive;
+
+use crate::Schematic;
+
+pub enum NumberCount {
+    Exactly(usize),
+    Between(RangeInclusive<usize>),
+}
+
+impl NumberCount {
+    fn matches(&self, count: usize) -> bool {
+        match self {
+            NumberCount::Exactly(expected) => count == *expected,
+            NumberCount::Between(range) => range.contains(&count),
+        }
+    }
+}
+
+pub enum Combine {
+    Product,
+    Sum,
+}
+
+impl Combine {
+    fn apply(&self, numbers: &[u32]) -> u64 {
+        let numbers = numbers.iter().map(|number| *number as u64);
+        match self {
+            Combine::Product => numbers.product(),
+            Combine::Sum => numbers.sum(),
+        }
+    }
+}
+
+pub struct Gear {
+    pub x: usize,
+    pub y: usize,
+    pub symbol: char,
+    pub numbers: Vec<u32>,
+    pub value: u64,
+}
+
+/// Describes which symbols are gears, how many numbers must touch them and
+/// how those numbers combine into the gear's value.
+pub struct GearRule {
+    symbols: Vec<char>,
+    count: NumberCount,
+    combine: Combine,
+}
+
+impl GearRule {
+    pub fn new(symbols: &[char], count: NumberCount, combine: Combine) -> GearRule {
+        GearRule { symbols: symbols.to_vec(), count, combine }
+    }
+
+    /// The puzzle's rule: a `*` touching exactly two numbers, multiplied.
+    pub fn gear_ratio() -> GearRule {
+        GearRule::new(&['*'], NumberCount::Exactly(2), Combine::Product)
+    }
+
+    pub fn find_gears(&self, schematic: &Schematic) -> Vec<Gear> {
+        schematic.symbols.iter()
+        .filter(|symbol| self.symbols.contains(&symbol.symbol))
+        .filter_map(|symbol| {
+            let numbers: Vec<_> = schematic.adjacent_numbers(symbol.x, symbol.y).iter()
+                .map(|index| schematic.number_boxes[*index].number)
+                .collect();
+            if !self.count.matches(numbers.len()) {
+                return None;
+            }
+            let value = self.combine.apply(&numbers);
+            Some(Gear { x: symbol.x, y: symbol.y, symbol: symbol.symbol, numbers, value })
+        })
+        .collect()
+    }
+
+    pub fn total(&self, schematic: &Schematic) -> u64 {
+        self.find_gears(schematic).iter().map(|gear| gear.value).sum()
+    }
+}
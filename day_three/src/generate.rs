@@ -0,0 +1,32 @@
+use common::Rng;
+
+const SYMBOLS: [u8; 10] = *b"*#+$/@=%&-";
+
+/// A `size` by `size` schematic of numbers up to three digits long and
+/// scattered symbols.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut result = String::with_capacity((size + 1) * size);
+    for _ in 0..size {
+        let mut row = vec![b'.'; size];
+        let mut x = 0;
+        while x < size {
+            if rng.chance(0.15) {
+                let length = (rng.between(1, 3) as usize).min(size - x);
+                row[x] = b'0' + rng.between(1, 9) as u8;
+                for digit in &mut row[x + 1..x + length] {
+                    *digit = b'0' + rng.below(10) as u8;
+                }
+                x += length + 1;
+            } else {
+                if rng.chance(0.06) {
+                    row[x] = *rng.choose(&SYMBOLS);
+                }
+                x += 1;
+            }
+        }
+        result.push_str(std::str::from_utf8(&row).unwrap());
+        result.push('\n');
+    }
+    result
+}
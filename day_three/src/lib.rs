@@ -1,12 +1,23 @@
+mod gear;
+mod generate;
+pub mod reference;
+mod render;
+
+pub use gear::{Combine, Gear, GearRule, NumberCount};
+pub use generate::generate;
+pub use render::{render, RenderStyle};
+
+use common::{ensure_ascii, normalise, ParseError};
+
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
     }
 }
 
@@ -17,84 +28,104 @@ struct NumberBox {
     number: u32
 }
 
-impl NumberBox {
-    fn is_adjacent_to(&self, x: usize, y: usize) -> bool {
-        if x > self.x_max + 1 {
-            return false;
-        }
-        if self.x_min > 0 && x < self.x_min - 1 {
-            return false;
-        }
-        if y > self.y + 1 {
-            return false;
-        }
-        if self.y > 0 && y < self.y - 1 {
-            return false;
-        }
-        true
-    }
-}
-
 struct Symbol {
     x: usize,
     y: usize,
     symbol: char
 }
 
-fn part_one(input: &str) -> u32 {
-    let (number_boxes, symbols) = parse_input(input);
-    number_boxes.iter()
-    .map(|num_box| {
-        if symbols.iter().any(|symbol| {
-            num_box.is_adjacent_to(symbol.x, symbol.y)
-        }) {
-            return num_box.number;
-        }
-        0
-    })
-    .sum()
+pub struct Schematic {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<usize>>,
+    number_boxes: Vec<NumberBox>,
+    symbols: Vec<Symbol>,
 }
 
-fn part_two(input: &str) -> u32 {
-    let (number_boxes, symbols) = parse_input(input);
-    symbols.iter()
-    .map(|symbol| {
-        if symbol.symbol != '*' {
-            return 0;
+impl Schematic {
+    pub fn parse_new(input: &str) -> Result<Schematic, ParseError> {
+        ensure_ascii(input)?;
+        let (number_boxes, symbols) = parse_input(input)?;
+        let width = input.lines().map(str::len).max().unwrap_or(0);
+        let height = input.lines().count();
+        let mut cells = vec![None; width * height];
+        for (index, num_box) in number_boxes.iter().enumerate() {
+            for x in num_box.x_min..=num_box.x_max {
+                cells[num_box.y * width + x] = Some(index);
+            }
+        }
+        Ok(Schematic { width, height, cells, number_boxes, symbols })
+    }
+
+    fn number_at(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells[y * self.width + x]
+    }
+
+    fn adjacent_numbers(&self, x: usize, y: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        for neighbour_y in y.saturating_sub(1)..=y + 1 {
+            for neighbour_x in x.saturating_sub(1)..=x + 1 {
+                if let Some(index) = self.number_at(neighbour_x, neighbour_y) {
+                    if !result.contains(&index) {
+                        result.push(index);
+                    }
+                }
+            }
         }
-        let adjacent_boxes: Vec<_> = number_boxes.iter().filter(|num_box| {
-            num_box.is_adjacent_to(symbol.x, symbol.y)
-        }).collect();
-        if adjacent_boxes.len() != 2 {
-            return 0;
+        result
+    }
+
+    fn part_flags(&self) -> Vec<bool> {
+        let mut is_part = vec![false; self.number_boxes.len()];
+        for symbol in &self.symbols {
+            for index in self.adjacent_numbers(symbol.x, symbol.y) {
+                is_part[index] = true;
+            }
         }
-        adjacent_boxes[0].number * adjacent_boxes[1].number
-    })
-    .sum()
+        is_part
+    }
 }
 
-fn parse_input(input: &str) -> (Vec<NumberBox>, Vec<Symbol>) {
+fn part_one(input: &str) -> Result<u64, ParseError> {
+    let schematic = Schematic::parse_new(&normalise(input))?;
+    Ok(schematic.number_boxes.iter().zip(schematic.part_flags())
+    .filter(|(_, is_part)| *is_part)
+    .map(|(num_box, _)| num_box.number as u64)
+    .sum())
+}
+
+fn part_two(input: &str) -> Result<u64, ParseError> {
+    let schematic = Schematic::parse_new(&normalise(input))?;
+    Ok(GearRule::gear_ratio().total(&schematic))
+}
+
+fn parse_input(input: &str) -> Result<(Vec<NumberBox>, Vec<Symbol>), ParseError> {
     let mut number_boxes: Vec<NumberBox> = Vec::new();
     let mut symbols: Vec<Symbol> = Vec::new();
     for (y, line) in input.lines().enumerate() {
         let mut current_number: u32 = 0;
         let mut parsing_number = false;
         let mut x_min = 0;
-        for (x, character) in line.chars().enumerate() {
+        for (x, character) in line.bytes().enumerate() {
             if character.is_ascii_digit() {
                 if !parsing_number {
                     x_min = x;
                     parsing_number = true;
                     current_number = 0;
                 }
-                current_number = current_number * 10 + character.to_digit(10).unwrap();
+                current_number = current_number.checked_mul(10)
+                    .and_then(|number| number.checked_add((character - b'0') as u32))
+                    .ok_or_else(|| ParseError::new(y + 1, x_min + 1, "number is too large"))?;
             } else {
                 if parsing_number {
                     number_boxes.push(NumberBox{x_min, x_max: x-1, y, number: current_number});
                     parsing_number = false;
                 }
-                if character != '.' {
-                    symbols.push(Symbol { x, y, symbol: character })
+                if character != b'.' {
+                    symbols.push(Symbol { x, y, symbol: character as char })
                 }
             }
         }
@@ -102,7 +133,7 @@ fn parse_input(input: &str) -> (Vec<NumberBox>, Vec<Symbol>) {
             number_boxes.push(NumberBox{x_min, x_max: line.len()-1, y, number: current_number});
         }
     }
-    (number_boxes, symbols)
+    Ok((number_boxes, symbols))
 }
 
 #[cfg(test)]
@@ -127,12 +158,58 @@ mod tests {
     #[test]
     fn it_calculates_the_sum_of_part_numbers() {
         let result = part_one(test_input());
-        assert_eq!(result, 4361);
+        assert_eq!(result, Ok(4361));
     }
 
     #[test]
     fn it_calculates_the_sum_of_adjacent_gear_products() {
         let result = part_two(test_input());
-        assert_eq!(result, 467835);
+        assert_eq!(result, Ok(467835));
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", test_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(4361));
+        assert_eq!(part_two(&input), Ok(467835));
+    }
+
+    #[test]
+    fn it_applies_custom_gear_rules() {
+        let schematic = Schematic::parse_new(test_input()).unwrap();
+        let rule = GearRule::new(&['*', '#', '+', '$'], NumberCount::Between(1..=2), Combine::Sum);
+        assert_eq!(rule.total(&schematic), 4361);
+        let rule = GearRule::new(&['*'], NumberCount::Exactly(1), Combine::Product);
+        assert_eq!(rule.total(&schematic), 617);
+    }
+
+    fn tiled_input(repeats: usize) -> String {
+        let mut result = String::new();
+        for _ in 0..repeats {
+            for line in test_input().lines() {
+                result.push_str(&line.repeat(repeats));
+                result.push('\n');
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn it_handles_large_schematics() {
+        let input = tiled_input(200);
+        assert_eq!(part_one(&input), Ok(4361 * 200 * 200));
+        assert_eq!(part_two(&input), Ok(467835 * 200 * 200));
+    }
+
+    #[test]
+    fn it_rejects_non_ascii_schematics() {
+        let result = part_one("467..114..\n..é*...12");
+        assert_eq!(result, Err(ParseError::new(2, 3, "unexpected non-ASCII character 'é'")));
+    }
+
+    #[test]
+    fn it_rejects_numbers_that_do_not_fit() {
+        let result = part_one("467..114..\n..*.99999999999");
+        assert_eq!(result, Err(ParseError::new(2, 5, "number is too large")));
     }
 }
@@ -0,0 +1,62 @@
+struct Number {
+    x_min: usize,
+    x_max: usize,
+    y: usize,
+    value: u64,
+}
+
+impl Number {
+    fn touches(&self, x: usize, y: usize) -> bool {
+        self.y.abs_diff(y) <= 1 && x + 1 >= self.x_min && x <= self.x_max + 1
+    }
+}
+
+fn parse(input: &str) -> (Vec<Number>, Vec<(usize, usize, char)>) {
+    let mut numbers = Vec::new();
+    let mut symbols = Vec::new();
+    for (y, line) in input.lines().enumerate() {
+        let characters: Vec<_> = line.chars().collect();
+        let mut x = 0;
+        while x < characters.len() {
+            if characters[x].is_ascii_digit() {
+                let start = x;
+                while x < characters.len() && characters[x].is_ascii_digit() {
+                    x += 1;
+                }
+                let value = characters[start..x].iter().collect::<String>().parse().unwrap();
+                numbers.push(Number { x_min: start, x_max: x - 1, y, value });
+                continue;
+            }
+            if characters[x] != '.' {
+                symbols.push((x, y, characters[x]));
+            }
+            x += 1;
+        }
+    }
+    (numbers, symbols)
+}
+
+/// Tests every number against every symbol.
+pub fn part_one(input: &str) -> u64 {
+    let (numbers, symbols) = parse(input);
+    numbers.iter()
+    .filter(|number| symbols.iter().any(|(x, y, _)| number.touches(*x, *y)))
+    .map(|number| number.value)
+    .sum()
+}
+
+/// Tests every `*` against every number.
+pub fn part_two(input: &str) -> u64 {
+    let (numbers, symbols) = parse(input);
+    symbols.iter()
+    .filter(|(_, _, symbol)| *symbol == '*')
+    .map(|(x, y, _)| {
+        let touching: Vec<_> = numbers.iter().filter(|number| number.touches(*x, *y)).collect();
+        if touching.len() == 2 {
+            touching[0].value * touching[1].value
+        } else {
+            0
+        }
+    })
+    .sum()
+}
@@ -0,0 +1,134 @@
+use crate::{GearRule, Schematic};
+
+const RESET: &str = "\x1b[0m";
+const PART_COLOUR: &str = "\x1b[32m";
+const NON_PART_COLOUR: &str = "\x1b[31m";
+const GEAR_COLOUR: &str = "\x1b[1;33m";
+
+const PART_MARKER: char = '=';
+const NON_PART_MARKER: char = 'x';
+const GEAR_MARKER: char = '^';
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    Ansi,
+    Plain,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Symbol(char),
+    Gear(char),
+    Part(char),
+    NonPart(char),
+}
+
+/// Reprints the schematic highlighting part numbers, non-part numbers and
+/// gears. Plain style prints a marker line under each row instead of colour.
+pub fn render(schematic: &Schematic, rule: &GearRule, style: RenderStyle) -> String {
+    let mut grid = vec![Cell::Empty; schematic.width * schematic.height];
+    let part_flags = schematic.part_flags();
+    for (num_box, is_part) in schematic.number_boxes.iter().zip(part_flags) {
+        let width = num_box.x_max - num_box.x_min + 1;
+        let digits = format!("{:0width$}", num_box.number);
+        for (offset, digit) in digits.chars().enumerate() {
+            grid[num_box.y * schematic.width + num_box.x_min + offset] = if is_part {
+                Cell::Part(digit)
+            } else {
+                Cell::NonPart(digit)
+            };
+        }
+    }
+    for symbol in &schematic.symbols {
+        grid[symbol.y * schematic.width + symbol.x] = Cell::Symbol(symbol.symbol);
+    }
+    for gear in rule.find_gears(schematic) {
+        grid[gear.y * schematic.width + gear.x] = Cell::Gear(gear.symbol);
+    }
+
+    let mut result = String::new();
+    for row in grid.chunks(schematic.width.max(1)).take(schematic.height) {
+        match style {
+            RenderStyle::Ansi => render_ansi_row(row, &mut result),
+            RenderStyle::Plain => render_plain_row(row, &mut result),
+        }
+    }
+    result
+}
+
+fn cell_char(cell: &Cell) -> char {
+    match cell {
+        Cell::Empty => '.',
+        Cell::Symbol(c) | Cell::Gear(c) | Cell::Part(c) | Cell::NonPart(c) => *c,
+    }
+}
+
+fn render_ansi_row(row: &[Cell], result: &mut String) {
+    for cell in row {
+        let colour = match cell {
+            Cell::Part(_) => Some(PART_COLOUR),
+            Cell::NonPart(_) => Some(NON_PART_COLOUR),
+            Cell::Gear(_) => Some(GEAR_COLOUR),
+            Cell::Empty | Cell::Symbol(_) => None,
+        };
+        match colour {
+            Some(colour) => {
+                result.push_str(colour);
+                result.push(cell_char(cell));
+                result.push_str(RESET);
+            }
+            None => result.push(cell_char(cell)),
+        }
+    }
+    result.push('\n');
+}
+
+fn render_plain_row(row: &[Cell], result: &mut String) {
+    result.extend(row.iter().map(cell_char));
+    result.push('\n');
+    let markers: String = row.iter()
+    .map(|cell| match cell {
+        Cell::Part(_) => PART_MARKER,
+        Cell::NonPart(_) => NON_PART_MARKER,
+        Cell::Gear(_) => GEAR_MARKER,
+        Cell::Empty | Cell::Symbol(_) => ' ',
+    })
+    .collect();
+    let markers = markers.trim_end();
+    if !markers.is_empty() {
+        result.push_str(markers);
+        result.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+
+    #[test]
+    fn it_marks_parts_and_gears_in_plain_text() {
+        let schematic = Schematic::parse_new(indoc! {"
+            12..7
+            .*...
+            3...."}).unwrap();
+        let result = render(&schematic, &GearRule::gear_ratio(), RenderStyle::Plain);
+        assert_eq!(result, indoc! {"
+            12..7
+            ==  x
+            .*...
+             ^
+            3....
+            =
+            "});
+    }
+
+    #[test]
+    fn it_colours_parts_and_gears() {
+        let schematic = Schematic::parse_new("1*2.3").unwrap();
+        let result = render(&schematic, &GearRule::gear_ratio(), RenderStyle::Ansi);
+        assert_eq!(result, "\x1b[32m1\x1b[0m\x1b[1;33m*\x1b[0m\x1b[32m2\x1b[0m.\x1b[31m3\x1b[0m\n");
+    }
+}
@@ -0,0 +1,115 @@
+use std::{collections::BTreeMap, fmt::{self, Display}};
+
+use crate::{Bag, CubeSet, Game};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColourStats {
+    pub colour: String,
+    pub draws: usize,
+    pub max: u32,
+    pub mean: f64,
+    /// Ids of the games that need the full `max`, and so set the smallest bag.
+    pub binding_games: Vec<u32>,
+    /// `(limit, feasible games)` at each limit where the count changes,
+    /// with every other colour unconstrained.
+    pub feasibility_curve: Vec<(u32, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub games: usize,
+    pub colours: Vec<ColourStats>,
+    pub smallest_bag: Bag,
+}
+
+pub fn analyse(games: &[Game]) -> Analysis {
+    let minimum_sets: Vec<_> = games.iter().map(Game::minimum_set).collect();
+    let smallest_set = minimum_sets.iter().fold(CubeSet::default(), |acc, set| acc.union_max(set));
+
+    let mut draws: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+    for round in games.iter().flat_map(|game| &game.rounds) {
+        for (colour, count) in round.colours() {
+            draws.entry(colour).or_default().push(count);
+        }
+    }
+
+    let colours = draws.iter()
+    .map(|(colour, counts)| {
+        let max = smallest_set.get(colour);
+        let binding_games = games.iter().zip(&minimum_sets)
+            .filter(|(_, set)| set.get(colour) == max)
+            .map(|(game, _)| game.id)
+            .collect();
+        let mut needed: Vec<_> = minimum_sets.iter().map(|set| set.get(colour)).collect();
+        needed.sort();
+        let mut feasibility_curve: Vec<(u32, usize)> = Vec::new();
+        for (index, limit) in needed.iter().enumerate() {
+            match feasibility_curve.last_mut() {
+                Some((last_limit, count)) if last_limit == limit => *count = index + 1,
+                _ => feasibility_curve.push((*limit, index + 1)),
+            }
+        }
+        ColourStats {
+            colour: colour.to_string(),
+            draws: counts.len(),
+            max,
+            mean: counts.iter().map(|count| u64::from(*count)).sum::<u64>() as f64 / counts.len() as f64,
+            binding_games,
+            feasibility_curve,
+        }
+    })
+    .collect();
+
+    Analysis { games: games.len(), colours, smallest_bag: Bag::new(smallest_set) }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Games: {}", self.games)?;
+        writeln!(f, "Smallest bag for every game: {}", self.smallest_bag)?;
+        for stats in &self.colours {
+            writeln!(f, "{}: {} draws, max {}, mean {:.2}", stats.colour, stats.draws, stats.max, stats.mean)?;
+            let binding: Vec<_> = stats.binding_games.iter().map(u32::to_string).collect();
+            writeln!(f, "  binding games: {}", binding.join(", "))?;
+            writeln!(f, "  feasible games by limit:")?;
+            for (limit, count) in &stats.feasibility_curve {
+                writeln!(f, "    {limit:>4}: {count}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+
+    use crate::parse_games;
+
+    #[test]
+    fn it_analyses_the_game_log() {
+        let games = parse_games(indoc! {"
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue
+            Game 2: 1 blue, 2 green; 4 blue, 1 red
+            Game 3: 6 blue, 2 red"}, None).unwrap();
+        let analysis = analyse(&games);
+        assert_eq!(analysis.smallest_bag, Bag::parse_new("blue=6,green=2,red=4").unwrap());
+        let blue = &analysis.colours[0];
+        assert_eq!(blue.colour, "blue");
+        assert_eq!(blue.draws, 5);
+        assert_eq!(blue.mean, 4.0);
+        assert_eq!(blue.binding_games, vec![1, 3]);
+        assert_eq!(blue.feasibility_curve, vec![(4, 1), (6, 3)]);
+        let green = &analysis.colours[1];
+        assert_eq!(green.binding_games, vec![1, 2]);
+        assert_eq!(green.feasibility_curve, vec![(0, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn it_averages_counts_too_large_to_sum_in_u32() {
+        let games = parse_games("Game 1: 4294967295 red; 4294967295 red", None).unwrap();
+        assert_eq!(analyse(&games).colours[0].mean, 4294967295.0);
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::{self, Display};
+
+use common::ParseError;
+
+use crate::{CubeSet, Game};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub game: u32,
+    pub round: usize,
+    pub colour: String,
+    pub count: u32,
+    pub limit: u32,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}, round {}: {} {} exceeds the limit of {}",
+            self.game, self.round, self.count, self.colour, self.limit)
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<_> = self.limits.colours()
+            .map(|(colour, count)| format!("{colour}={count}"))
+            .collect();
+        write!(f, "{}", entries.join(","))
+    }
+}
+
+/// The cubes known to be in the bag. Colours not in the bag have a limit
+/// of zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    limits: CubeSet,
+}
+
+impl Bag {
+    pub fn new(limits: CubeSet) -> Bag {
+        Bag { limits }
+    }
+
+    /// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+    pub fn puzzle() -> Bag {
+        let mut limits = CubeSet::default();
+        limits.insert("red", 12);
+        limits.insert("green", 13);
+        limits.insert("blue", 14);
+        Bag { limits }
+    }
+
+    /// Reads `colour count` or `colour=count` entries separated by commas or
+    /// newlines, e.g. `red=12,green=13,blue=14`. Lines starting with `#`
+    /// are ignored.
+    pub fn parse_new(config: &str) -> Result<Bag, ParseError> {
+        let mut limits = CubeSet::default();
+        for (index, line) in config.lines().enumerate() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            for entry in line.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+                let error = |message: String| ParseError::new(index + 1, 1, message);
+                let mut parts = entry.split(|c: char| c == '=' || c.is_whitespace())
+                    .filter(|part| !part.is_empty());
+                let (Some(colour), Some(count), None) = (parts.next(), parts.next(), parts.next()) else {
+                    return Err(error(format!("expected '<colour>=<count>', found {entry:?}")));
+                };
+                let count = count.parse::<u32>()
+                    .map_err(|_| error(format!("invalid cube count {count:?} for {colour:?}")))?;
+                limits.insert(colour, count);
+            }
+        }
+        Ok(Bag { limits })
+    }
+
+    pub fn colours(&self) -> Vec<&str> {
+        self.limits.colours().map(|(colour, _)| colour).collect()
+    }
+
+    pub fn violations(&self, game: &Game) -> Vec<Violation> {
+        let mut result = Vec::new();
+        for (index, round) in game.rounds.iter().enumerate() {
+            for (colour, count) in round.colours() {
+                let limit = self.limits.get(colour);
+                if count > limit {
+                    result.push(Violation {
+                        game: game.id,
+                        round: index + 1,
+                        colour: colour.to_string(),
+                        count,
+                        limit,
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    pub fn is_feasible(&self, game: &Game) -> bool {
+        self.violations(game).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_bag_configuration() {
+        let bag = Bag::parse_new("red=12, green 13\n# comment\nblue=14").unwrap();
+        assert_eq!(bag, Bag::puzzle());
+    }
+
+    #[test]
+    fn it_rejects_bad_counts() {
+        let result = Bag::parse_new("red=12\nblue=lots");
+        assert_eq!(result, Err(ParseError::new(2, 1, "invalid cube count \"lots\" for \"blue\"")));
+    }
+
+    #[test]
+    fn it_explains_infeasible_rounds() {
+        let game = Game::parse_new("Game 3: 2 red; 5 yellow, 20 red", 1, None).unwrap();
+        let violations: Vec<_> = Bag::puzzle().violations(&game).iter().map(Violation::to_string).collect();
+        assert_eq!(violations, vec![
+            "Game 3, round 2: 20 red exceeds the limit of 12",
+            "Game 3, round 2: 5 yellow exceeds the limit of 0",
+        ]);
+    }
+}
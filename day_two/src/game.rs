@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, error::Error, io::BufRead};
+
+use common::{column_of, InputLines, ParseError};
+
+pub const DEFAULT_COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CubeSet {
+    counts: BTreeMap<String, u32>,
+}
+
+impl CubeSet {
+    pub fn get(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts.iter().map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    pub fn insert(&mut self, colour: &str, count: u32) {
+        self.counts.insert(colour.to_string(), count);
+    }
+
+    /// The smallest set that contains both this set and `other`.
+    pub fn union_max(&self, other: &CubeSet) -> CubeSet {
+        let mut result = self.clone();
+        for (colour, count) in other.colours() {
+            let existing = result.counts.entry(colour.to_string()).or_insert(0);
+            *existing = (*existing).max(count);
+        }
+        result
+    }
+
+    /// The product of the counts, or `None` if it overflows.
+    pub fn power(&self) -> Option<u64> {
+        self.counts.values().try_fold(1u64, |product, count| product.checked_mul((*count).into()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<CubeSet>,
+}
+
+impl Game {
+    /// Parses a `Game <id>: <count> <colour>, ...; ...` line, accepting only
+    /// the given colours, or any colour if none are given. `line_number` is
+    /// used for error positions.
+    pub fn parse_new(line: &str, line_number: usize, colours: Option<&[&str]>) -> Result<Game, ParseError> {
+        let error = |part: &str, message: String| {
+            ParseError::new(line_number, column_of(line, part), message)
+        };
+        let Some((header, rounds_text)) = line.split_once(':') else {
+            return Err(error(line, "expected ':' after the game id".to_string()));
+        };
+        let Some(id_text) = header.strip_prefix("Game ") else {
+            return Err(error(header, "expected line to start with 'Game '".to_string()));
+        };
+        let id = id_text.trim().parse::<u32>()
+            .map_err(|_| error(id_text, format!("invalid game id {:?}", id_text.trim())))?;
+
+        let mut rounds = Vec::new();
+        for round_text in rounds_text.split(';') {
+            let mut round = CubeSet::default();
+            for draw in round_text.split(',') {
+                let mut parts = draw.split_whitespace();
+                let (Some(count_text), Some(colour), None) = (parts.next(), parts.next(), parts.next()) else {
+                    return Err(error(draw, format!("expected '<count> <colour>', found {:?}", draw.trim())));
+                };
+                let count = count_text.parse::<u32>()
+                    .map_err(|_| error(count_text, format!("invalid cube count {count_text:?}")))?;
+                if colours.is_some_and(|colours| !colours.contains(&colour)) {
+                    return Err(error(colour, format!("unknown colour {colour:?}")));
+                }
+                if round.counts.contains_key(colour) {
+                    return Err(error(colour, format!("colour {colour:?} drawn twice in one round")));
+                }
+                round.insert(colour, count);
+            }
+            rounds.push(round);
+        }
+        Ok(Game { id, rounds })
+    }
+
+    pub fn minimum_set(&self) -> CubeSet {
+        self.rounds.iter().fold(CubeSet::default(), |acc, round| acc.union_max(round))
+    }
+
+    /// The power of the minimum set, failing with an error at `line_number`
+    /// if it overflows.
+    pub fn minimum_power(&self, line_number: usize) -> Result<u64, ParseError> {
+        self.minimum_set().power()
+            .ok_or_else(|| ParseError::new(line_number, 1, "power of the minimum set overflows u64"))
+    }
+}
+
+pub fn parse_games(input: &str, colours: Option<&[&str]>) -> Result<Vec<Game>, ParseError> {
+    input.lines().enumerate()
+    .map(|(index, line)| Game::parse_new(line, index + 1, colours))
+    .collect()
+}
+
+/// Parses each game as its line is read, so only one is held at a time.
+pub fn read_games<'a>(
+    input: impl BufRead + 'a,
+    colours: Option<&'a [&'a str]>,
+) -> impl Iterator<Item = Result<Game, Box<dyn Error>>> + 'a {
+    InputLines::new(input).enumerate()
+    .map(move |(index, line)| Ok(Game::parse_new(&line?, index + 1, colours)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    fn parse(line: &str) -> Result<Game, ParseError> {
+        Game::parse_new(line, 3, Some(&DEFAULT_COLOURS))
+    }
+
+    #[test]
+    fn it_keeps_round_boundaries() {
+        let game = parse("Game 7: 3 blue, 4 red; 2 green").unwrap();
+        assert_eq!(game.id, 7);
+        assert_eq!(game.rounds.len(), 2);
+        assert_eq!(game.rounds[0].get("red"), 4);
+        assert_eq!(game.rounds[0].get("green"), 0);
+        assert_eq!(game.rounds[1].get("green"), 2);
+    }
+
+    #[test]
+    fn it_rejects_unknown_colours() {
+        let result = parse("Game 1: 3 blue, 4 purple");
+        assert_eq!(result, Err(ParseError::new(3, 19, "unknown colour \"purple\"")));
+    }
+
+    #[test]
+    fn it_rejects_malformed_counts() {
+        let result = parse("Game 1: 3 blue; x red");
+        assert_eq!(result, Err(ParseError::new(3, 17, "invalid cube count \"x\"")));
+        let result = parse("Game 1: 3 blue;; 1 red");
+        assert_eq!(result, Err(ParseError::new(3, 16, "expected '<count> <colour>', found \"\"")));
+    }
+
+    #[test]
+    fn it_rejects_malformed_headers() {
+        let result = parse("Game one: 3 blue");
+        assert_eq!(result, Err(ParseError::new(3, 6, "invalid game id \"one\"")));
+        let result = parse("Game 1 3 blue");
+        assert_eq!(result, Err(ParseError::new(3, 1, "expected ':' after the game id")));
+    }
+
+    fn games() -> impl Strategy<Value = Game> {
+        let round = prop::collection::btree_map(prop::sample::select(&DEFAULT_COLOURS[..]), 0u32..20, 1..=3)
+            .prop_map(|counts| {
+                let mut round = CubeSet::default();
+                for (colour, count) in counts {
+                    round.insert(colour, count);
+                }
+                round
+            });
+        (1u32..1000, prop::collection::vec(round, 1..6)).prop_map(|(id, rounds)| Game { id, rounds })
+    }
+
+    fn format_game(game: &Game) -> String {
+        let rounds: Vec<_> = game.rounds.iter()
+            .map(|round| round.colours().map(|(colour, count)| format!("{count} {colour}")).collect::<Vec<_>>().join(", "))
+            .collect();
+        format!("Game {}: {}", game.id, rounds.join("; "))
+    }
+
+    proptest! {
+        #[test]
+        fn formatted_games_parse_back_unchanged(game in games()) {
+            prop_assert_eq!(parse(&format_game(&game)), Ok(game));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use common::Rng;
+
+use crate::DEFAULT_COLOURS;
+
+/// A log of `size` games, each with one to six rounds drawing up to 20
+/// cubes of each colour.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut result = String::new();
+    for id in 1..=size {
+        let rounds: Vec<_> = (0..rng.between(1, 6))
+        .map(|_| {
+            let mut colours = DEFAULT_COLOURS;
+            rng.shuffle(&mut colours);
+            let drawn = rng.between(1, colours.len() as u64) as usize;
+            colours[..drawn].iter()
+            .map(|colour| format!("{} {colour}", rng.between(1, 20)))
+            .collect::<Vec<_>>()
+            .join(", ")
+        })
+        .collect();
+        result.push_str(&format!("Game {id}: {}\n", rounds.join("; ")));
+    }
+    result
+}
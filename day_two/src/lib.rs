@@ -1,61 +1,78 @@
-use std::collections::HashMap;
+use std::{error::Error, io::BufRead};
+
+use common::{normalise, ParseError};
+
+mod analysis;
+mod bag;
+mod game;
+mod generate;
+pub mod reference;
+
+pub use analysis::{analyse, Analysis, ColourStats};
+pub use bag::{Bag, Violation};
+pub use game::{parse_games, read_games, CubeSet, Game, DEFAULT_COLOURS};
+pub use generate::generate;
 
 pub struct Solution;
 
 impl common::Solution for Solution {
-    fn part_one(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_one(input))
+    fn part_one(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_one(input)?.into())
+    }
+
+    fn part_two(&self, input: &str) -> Result<common::Answer, Box<dyn std::error::Error>> {
+        Ok(part_two(input)?.into())
     }
 
-    fn part_two(&self, input: &str) -> Box<dyn std::fmt::Display> {
-        Box::new(part_two(input))
+    fn streaming(&self) -> Option<&dyn common::StreamingSolution> {
+        Some(self)
     }
 }
 
-fn part_one(input: &str) -> u32 {
-    let limits: HashMap<&str, u32> = HashMap::from([
-        ("red", 12),
-        ("green", 13),
-        ("blue", 14)
-    ]);
-    input.lines()
-    .map(|line| {
-        let parts: Vec<_> = line.split(":").collect();
-        let game_id = parts[0].split_whitespace().collect::<Vec<_>>()[1].parse::<u32>().unwrap();
-        let mut cube_counts = parts[1].split(&[';', ','][..]);
-        if cube_counts.any(|cube_count| {
-            let mut cube_info = cube_count.trim().split_whitespace();
-            let cube_count = cube_info.next().unwrap().parse::<u32>().unwrap();
-            let cube_colour = cube_info.next().unwrap();
-            cube_count > *limits.get(cube_colour).unwrap()
-        }) {
-            return 0;
-        }           
-        game_id
-    })
-    .sum()
+impl common::StreamingSolution for Solution {
+    fn part_one(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        let bag = Bag::puzzle();
+        let mut total = 0u64;
+        for (index, game) in read_games(input, Some(&DEFAULT_COLOURS)).enumerate() {
+            let game = game?;
+            if bag.is_feasible(&game) {
+                total = add_to_total(total, game.id, index + 1)?;
+            }
+        }
+        Ok(total.into())
+    }
+
+    fn part_two(&self, input: &mut dyn BufRead) -> Result<common::Answer, Box<dyn Error>> {
+        let mut total = 0u64;
+        for (index, game) in read_games(input, Some(&DEFAULT_COLOURS)).enumerate() {
+            total = add_to_total(total, game?.minimum_power(index + 1)?, index + 1)?;
+        }
+        Ok(total.into())
+    }
 }
 
-fn part_two(input: &str) -> u32 {
-    input.lines()
-    .map(|line| {
-        let mut min_cubes: HashMap<&str, u32> = HashMap::new();
-
-        let parts: Vec<_> = line.split(":").collect();
-        let cube_counts = parts[1].split(&[';', ','][..]);
-        cube_counts.for_each(|cube_count| {
-            let mut cube_info = cube_count.trim().split_whitespace();
-            let cube_count = cube_info.next().unwrap().parse::<u32>().unwrap();
-            let cube_colour = cube_info.next().unwrap();
-            min_cubes.entry(cube_colour).and_modify(|existing| {
-              if cube_count > *existing {
-                *existing = cube_count
-              }  
-            }).or_insert(cube_count);
-        });
-        min_cubes.values().cloned().reduce(|acc, e| acc * e).unwrap()
-    })
-    .sum()
+fn add_to_total(total: u64, value: impl Into<u64>, line_number: usize) -> Result<u64, ParseError> {
+    total.checked_add(value.into())
+        .ok_or_else(|| ParseError::new(line_number, 1, "total overflows u64"))
+}
+
+/// Sums the ids of the games the bag can hold, taking the games to be one
+/// per line in input order when reporting an overflow.
+pub fn sum_feasible_ids(games: &[Game], bag: &Bag) -> Result<u64, ParseError> {
+    games.iter().enumerate()
+    .filter(|(_, game)| bag.is_feasible(game))
+    .try_fold(0, |total, (index, game)| add_to_total(total, game.id, index + 1))
+}
+
+fn part_one(input: &str) -> Result<u64, ParseError> {
+    let games = parse_games(&normalise(input), Some(&DEFAULT_COLOURS))?;
+    sum_feasible_ids(&games, &Bag::puzzle())
+}
+
+fn part_two(input: &str) -> Result<u64, ParseError> {
+    let games = parse_games(&normalise(input), Some(&DEFAULT_COLOURS))?;
+    games.iter().enumerate()
+    .try_fold(0, |total, (index, game)| add_to_total(total, game.minimum_power(index + 1)?, index + 1))
 }
 
 #[cfg(test)]
@@ -75,12 +92,58 @@ mod tests {
     #[test]
     fn it_sums_ids_of_possible_games() {
         let result = part_one(test_input());
-        assert_eq!(result, 8);
+        assert_eq!(result, Ok(8));
     }
 
     #[test]
     fn it_sums_power_of_minimum_sets() {
         let result = part_two(test_input());
-        assert_eq!(result, 2286);
+        assert_eq!(result, Ok(2286));
+    }
+
+    #[test]
+    fn it_accepts_windows_style_input() {
+        let input = format!("\u{feff}{}\r\n\r\n", test_input().replace('\n', " \r\n"));
+        assert_eq!(part_one(&input), Ok(8));
+        assert_eq!(part_two(&input), Ok(2286));
+    }
+
+    #[test]
+    fn it_streams_games_one_line_at_a_time() {
+        use common::{Answer, StreamingSolution};
+        let input = format!("{}\n\n", test_input());
+        assert_eq!(StreamingSolution::part_one(&Solution, &mut input.as_bytes()).unwrap(), Answer::Integer(8));
+        assert_eq!(StreamingSolution::part_two(&Solution, &mut input.as_bytes()).unwrap(), Answer::Integer(2286));
+        let result = StreamingSolution::part_one(&Solution, &mut "Game 1: 3 blue\nGame 2 3 red".as_bytes());
+        assert_eq!(result.unwrap_err().to_string(), "line 2, column 1: expected ':' after the game id");
+    }
+
+    #[test]
+    fn it_gives_the_same_totals_streaming_or_not() {
+        use common::{Answer, StreamingSolution};
+        let input = "Game 4294967295: 1 red\nGame 4294967295: 1 red";
+        let streamed = StreamingSolution::part_one(&Solution, &mut input.as_bytes()).unwrap();
+        assert_eq!(streamed, Answer::Integer(2 * u32::MAX as u64));
+        assert_eq!(common::Solution::part_one(&Solution, input).unwrap(), streamed);
+        let input = "Game 1: 100000 red, 100000 green, 100000 blue\nGame 2: 100000 red, 100000 green, 100000 blue";
+        let streamed = StreamingSolution::part_two(&Solution, &mut input.as_bytes()).unwrap();
+        assert_eq!(streamed, Answer::Integer(2 * 100000u64.pow(3)));
+        assert_eq!(common::Solution::part_two(&Solution, input).unwrap(), streamed);
+    }
+
+    #[test]
+    fn it_reports_powers_that_overflow() {
+        use common::StreamingSolution;
+        let input = "Game 1: 100000 red, 100000 green, 100000 blue\nGame 2: 4294967295 red, 4294967295 green, 2 blue";
+        assert_eq!(part_two(input), Err(ParseError::new(2, 1, "power of the minimum set overflows u64")));
+        let result = StreamingSolution::part_two(&Solution, &mut input.as_bytes());
+        assert_eq!(result.unwrap_err().to_string(), "line 2, column 1: power of the minimum set overflows u64");
+    }
+
+    #[test]
+    fn it_sums_ids_of_games_possible_with_any_bag() {
+        let games = parse_games(test_input(), None).unwrap();
+        let bag = Bag::parse_new("red=4,green=3,blue=6").unwrap();
+        assert_eq!(sum_feasible_ids(&games, &bag), Ok(1 + 2));
     }
 }
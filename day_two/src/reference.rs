@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+fn draws(line: &str) -> (u32, Vec<(u32, &str)>) {
+    let (header, rest) = line.split_once(": ").unwrap();
+    let id = header["Game ".len()..].parse().unwrap();
+    let draws = rest.split(&[';', ','][..])
+        .map(|draw| {
+            let (count, colour) = draw.trim().split_once(' ').unwrap();
+            (count.parse().unwrap(), colour)
+        })
+        .collect();
+    (id, draws)
+}
+
+/// Checks every draw of every game against the puzzle's bag.
+pub fn part_one(input: &str) -> u64 {
+    let limits = HashMap::from([("red", 12), ("green", 13), ("blue", 14)]);
+    input.lines()
+    .map(draws)
+    .filter(|(_, draws)| draws.iter().all(|(count, colour)| count <= &limits[colour]))
+    .map(|(id, _)| u64::from(id))
+    .sum()
+}
+
+/// Multiplies the largest draw of each colour seen in each game.
+pub fn part_two(input: &str) -> u64 {
+    input.lines()
+    .map(draws)
+    .map(|(_, draws)| {
+        let mut largest: HashMap<&str, u32> = HashMap::new();
+        for (count, colour) in draws {
+            let entry = largest.entry(colour).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+        largest.values().map(|count| u64::from(*count)).product::<u64>()
+    })
+    .sum()
+}
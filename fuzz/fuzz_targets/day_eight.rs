@@ -0,0 +1,10 @@
+#![no_main]
+
+use day_eight::Network;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(network) = Network::parse_new(input) {
+        let _ = network.steps("AAA", "ZZZ");
+    }
+});
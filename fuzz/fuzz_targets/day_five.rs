@@ -0,0 +1,8 @@
+#![no_main]
+
+use day_five::Almanac;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Almanac::parse_new(input);
+});
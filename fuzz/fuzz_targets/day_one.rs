@@ -0,0 +1,12 @@
+#![no_main]
+
+use day_one::Vocabulary;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_one::calibrate(input, &Vocabulary::digits());
+    let _ = day_one::calibrate_lenient(input, &Vocabulary::english());
+    if let Ok(vocabulary) = Vocabulary::parse_new(input) {
+        let _ = day_one::calibrate(input, &vocabulary);
+    }
+});
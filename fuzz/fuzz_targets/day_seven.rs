@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_seven::parse_hands(input, false);
+    let _ = day_seven::parse_hands(input, true);
+});
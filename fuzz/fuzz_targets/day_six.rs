@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_six::parse_races(input);
+    let _ = day_six::parse_single_race(input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use day_three::Schematic;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Schematic::parse_new(input);
+});
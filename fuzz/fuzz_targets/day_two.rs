@@ -0,0 +1,10 @@
+#![no_main]
+
+use day_two::{Bag, DEFAULT_COLOURS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_two::parse_games(input, Some(&DEFAULT_COLOURS));
+    let _ = day_two::parse_games(input, None);
+    let _ = Bag::parse_new(input);
+});
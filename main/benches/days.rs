@@ -0,0 +1,106 @@
+use std::{fs, hint::black_box};
+
+use common::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// The seed every generated input is made from, fixed so that runs on
+/// different commits measure the same work.
+const SEED: u64 = 2023;
+
+struct Day {
+    number: u8,
+    solution: &'static dyn Solution,
+    /// Day one has no separate parsing step: it reads each line as it solves.
+    parse: Option<fn(&str)>,
+    generate: fn(usize, u64) -> String,
+    /// A generated size well above the real input's, within what each
+    /// generator can still answer.
+    large_size: usize,
+}
+
+fn days() -> Vec<Day> {
+    vec![
+        Day { number: 1, solution: &day_one::Solution, parse: None, generate: day_one::generate, large_size: 10_000 },
+        Day {
+            number: 2,
+            solution: &day_two::Solution,
+            parse: Some(|input| { black_box(day_two::parse_games(input, None)).ok(); }),
+            generate: day_two::generate,
+            large_size: 10_000,
+        },
+        Day {
+            number: 3,
+            solution: &day_three::Solution,
+            parse: Some(|input| { black_box(day_three::Schematic::parse_new(input)).ok(); }),
+            generate: day_three::generate,
+            large_size: 1_000,
+        },
+        Day {
+            number: 4,
+            solution: &day_four::Solution,
+            parse: Some(|input| { black_box(day_four::parse_cards(input)).ok(); }),
+            generate: day_four::generate,
+            large_size: 10_000,
+        },
+        Day {
+            number: 5,
+            solution: &day_five::Solution,
+            parse: Some(|input| { black_box(day_five::Almanac::parse_new(input)).ok(); }),
+            generate: day_five::generate,
+            large_size: 1_000,
+        },
+        Day {
+            number: 6,
+            solution: &day_six::Solution,
+            parse: Some(|input| { black_box(day_six::parse_races(input)).ok(); }),
+            generate: day_six::generate,
+            large_size: 4,
+        },
+        Day {
+            number: 7,
+            solution: &day_seven::Solution,
+            parse: Some(|input| { black_box(day_seven::parse_hands(input, false)).ok(); }),
+            generate: day_seven::generate,
+            large_size: 10_000,
+        },
+        Day {
+            number: 8,
+            solution: &day_eight::Solution,
+            parse: Some(|input| { black_box(day_eight::Network::parse_new(input)).ok(); }),
+            generate: day_eight::generate,
+            large_size: 10_000,
+        },
+    ]
+}
+
+/// Benchmarks are named `day N/<step>/<input>`, so a single day can be run
+/// with `cargo bench -p main -- "day 5/"`. The real input is skipped when
+/// its file is missing. To compare commits, run one with `--save-baseline
+/// before` and the other with `--baseline before`.
+fn bench_days(c: &mut Criterion) {
+    for day in days() {
+        let mut inputs = Vec::new();
+        let real_input = format!("{}/../input{}.txt", env!("CARGO_MANIFEST_DIR"), day.number);
+        if let Ok(input) = fs::read_to_string(real_input) {
+            inputs.push(("real".to_string(), input));
+        }
+        inputs.push((format!("generated {}", day.large_size), (day.generate)(day.large_size, SEED)));
+
+        let mut group = c.benchmark_group(format!("day {}", day.number));
+        for (name, input) in &inputs {
+            if let Some(parse) = day.parse {
+                group.bench_with_input(BenchmarkId::new("parse", name), input.as_str(), |b, input| b.iter(|| parse(input)));
+            }
+            group.bench_with_input(BenchmarkId::new("part one", name), input.as_str(), |b, input| {
+                b.iter(|| day.solution.part_one(input))
+            });
+            group.bench_with_input(BenchmarkId::new("part two", name), input.as_str(), |b, input| {
+                b.iter(|| day.solution.part_two(input))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
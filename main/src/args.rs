@@ -0,0 +1,91 @@
+use std::{collections::{HashMap, HashSet}, str::FromStr};
+
+const COMMANDS: [&str; 4] = ["generate", "analyse", "trace", "memory"];
+const VALUE_FLAGS: [&str; 5] = ["--vocabulary", "--bag", "--bag-file", "--size", "--seed"];
+
+/// The runner's command line: an optional command, an optional day number
+/// and any `--flag` or `--flag value` options, in any order.
+pub struct Args {
+    pub command: Option<String>,
+    pub day_number: u8,
+    flags: HashSet<String>,
+    values: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: impl Iterator<Item = String>) -> Args {
+        let mut command = None;
+        let mut day_number = None;
+        let mut flags = HashSet::new();
+        let mut values = HashMap::new();
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            if VALUE_FLAGS.contains(&arg.as_str()) {
+                if let Some(value) = args.next() {
+                    values.insert(arg, value);
+                }
+            } else if arg.starts_with("--") {
+                flags.insert(arg);
+            } else if command.is_none() && day_number.is_none() && COMMANDS.contains(&arg.as_str()) {
+                command = Some(arg);
+            } else if day_number.is_none() {
+                day_number = arg.parse::<u8>().ok();
+            }
+        }
+        Args { command, day_number: day_number.unwrap_or(0), flags, values }
+    }
+
+    pub fn has(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    pub fn value(&self, flag: &str) -> Option<&str> {
+        self.values.get(flag).map(String::as_str)
+    }
+
+    /// The value given for `flag`, or `default` if there was none.
+    pub fn parse_value<T: FromStr>(&self, flag: &str, default: T) -> Result<T, String> {
+        match self.value(flag) {
+            Some(value) => value.parse().map_err(|_| format!("invalid value {value:?} for {flag}")),
+            None => Ok(default),
+        }
+    }
+
+    pub fn is_command(&self, command: &str) -> bool {
+        self.command.as_deref() == Some(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Args {
+        Args::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn it_reads_flags_values_and_day_in_any_order() {
+        let args = parse("generate --size 5 3 --seed 9 --no-color");
+        assert!(args.is_command("generate"));
+        assert_eq!(args.day_number, 3);
+        assert_eq!(args.value("--size"), Some("5"));
+        assert_eq!(args.value("--seed"), Some("9"));
+        assert!(args.has("--no-color"));
+    }
+
+    #[test]
+    fn it_rejects_values_that_do_not_parse() {
+        let args = parse("generate 6 --size abc --seed -1");
+        assert_eq!(args.parse_value("--size", 100usize), Err("invalid value \"abc\" for --size".to_string()));
+        assert_eq!(args.parse_value("--seed", 0u64), Err("invalid value \"-1\" for --seed".to_string()));
+        assert_eq!(parse("generate 6").parse_value("--size", 100usize), Ok(100));
+    }
+
+    #[test]
+    fn it_defaults_to_every_day() {
+        let args = parse("");
+        assert_eq!(args.command, None);
+        assert_eq!(args.day_number, 0);
+    }
+}
@@ -1,14 +1,108 @@
-use std::{fs, env, collections::{BTreeMap, btree_map::Entry}};
+use std::{fs::{self, File}, env, error::Error, io::{self, BufReader, Read}, path::{Path, PathBuf}, process::ExitCode, collections::{BTreeMap, btree_map::Entry}};
 
-use common::Solution;
+use common::{normalise, Answer, Solution, StreamingSolution};
+use day_one::Vocabulary;
+use day_two::Bag;
+use day_four::{CardError, CascadeTrace, Tally};
+use day_three::{GearRule, RenderStyle, Schematic};
+use serde::Serialize;
 
-fn main() {
-    let args: Vec<_> = env::args().collect();
-    let day_number = match args.len() {
-        0 | 1 => 0,
-        _ => args[1].parse::<u8>().unwrap_or(0)
+mod args;
+mod memory;
+mod watch;
+
+use args::Args;
+use memory::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() -> ExitCode {
+    let args = Args::parse(env::args().skip(1));
+    let day_number = args.day_number;
+
+    if args.is_command("generate") {
+        return generate_input(&args);
+    }
+
+    if args.has("--watch") {
+        match solutions().get(&day_number) {
+            Some(solution) => watch::watch(day_number, *solution, args.has("--examples")),
+            None => eprintln!("--watch needs a day between 1 and {}", solutions().len()),
+        }
+        return ExitCode::FAILURE;
+    }
+
+    if args.is_command("memory") {
+        return for_each_day(day_number, profile_day);
+    }
+
+    if args.is_command("analyse") {
+        return analyse_day_two();
+    }
+
+    let wide = args.has("--u128");
+
+    if args.is_command("trace") {
+        return if wide {
+            trace_day_four::<u128>()
+        } else {
+            trace_day_four::<u64>()
+        };
+    }
+
+    if let Some(vocabulary_file) = args.value("--vocabulary") {
+        return calibrate_day_one(vocabulary_file);
+    }
+
+    if wide {
+        if day_number != 4 {
+            eprintln!("--u128 is only available for day 4");
+            return ExitCode::FAILURE;
+        }
+        return run_day_four_wide();
+    }
+
+    let bag_config = match (args.value("--bag"), args.value("--bag-file")) {
+        (Some(config), _) => Some(Ok(config.to_string())),
+        (None, Some(path)) => Some(
+            fs::read_to_string(path)
+                .map(|config| normalise(&config).into_owned())
+                .map_err(|error| format!("{path}: {error}")),
+        ),
+        (None, None) => None,
     };
+    if let Some(bag_config) = bag_config {
+        return match bag_config {
+            Ok(config) => check_day_two_bag(&config),
+            Err(error) => {
+                eprintln!("{error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if args.has("--render") {
+        if day_number != 3 {
+            eprintln!("--render is only available for day 3");
+            return ExitCode::FAILURE;
+        }
+        let style = if args.has("--no-color") {
+            RenderStyle::Plain
+        } else {
+            RenderStyle::Ansi
+        };
+        return render_day_three(style);
+    }
+
+    if args.has("--json") {
+        return for_each_day(day_number, print_day_json);
+    }
 
+    for_each_day(day_number, run_day)
+}
+
+fn solutions() -> BTreeMap<u8, &'static dyn Solution> {
     let mut days: BTreeMap<u8, &dyn Solution> = BTreeMap::new();
     days.insert(1, &day_one::Solution);
     days.insert(2, &day_two::Solution);
@@ -18,22 +112,302 @@ fn main() {
     days.insert(6, &day_six::Solution);
     days.insert(7, &day_seven::Solution);
     days.insert(8, &day_eight::Solution);
+    days
+}
 
+/// Runs the given day, or every day if there is no such day, failing if
+/// any of them did.
+fn for_each_day(day_number: u8, run: fn(&u8, &dyn Solution) -> ExitCode) -> ExitCode {
+    let mut days = solutions();
     match days.entry(day_number) {
         Entry::Occupied(day) => {
-            run_day(&day_number, *day.get());
+            run(&day_number, *day.get())
         },
         Entry::Vacant(_) => {
+            let mut exit_code = ExitCode::SUCCESS;
             for (day_number, solution) in days.iter() {
-                run_day(day_number, *solution);
+                if run(day_number, *solution) != ExitCode::SUCCESS {
+                    exit_code = ExitCode::FAILURE;
+                }
             }
+            exit_code
         },
-    } 
+    }
 }
 
-fn run_day(day_number: &u8, solution: &dyn Solution) {
-    let input_file = format!("input{day_number}.txt");
-    let input = fs::read_to_string(input_file).unwrap();
-    println!("Day {}, Part 1: {}", day_number, solution.part_one(&input));
-    println!("Day {}, Part 2: {}", day_number, solution.part_two(&input));
+fn input_file(day_number: &u8) -> String {
+    format!("input{day_number}.txt")
+}
+
+/// Opens an input file, naming it in the error if it cannot be.
+fn open_file(path: &Path) -> io::Result<File> {
+    File::open(path).map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", path.display())))
+}
+
+fn read_file(path: &Path) -> io::Result<String> {
+    let mut contents = String::new();
+    open_file(path)?.read_to_string(&mut contents)?;
+    Ok(normalise(&contents).into_owned())
+}
+
+fn read_input(day_number: &u8) -> io::Result<String> {
+    read_file(Path::new(&input_file(day_number)))
+}
+
+/// A day's input as both parts take it: read and normalised once for days
+/// that need all of it, or left for each part to stream.
+enum Input<'a> {
+    Text(String),
+    Stream(&'a Path, &'a dyn StreamingSolution),
+}
+
+fn load<'a>(path: &'a Path, solution: &'a dyn Solution) -> io::Result<Input<'a>> {
+    match solution.streaming() {
+        Some(streaming) => Ok(Input::Stream(path, streaming)),
+        None => Ok(Input::Text(read_file(path)?)),
+    }
+}
+
+/// Solves one part, reading the input a line at a time for days that can
+/// rather than loading it all first.
+fn solve(input: &io::Result<Input>, solution: &dyn Solution, part: u8) -> Result<Answer, Box<dyn Error>> {
+    match input {
+        Ok(Input::Text(input)) => match part {
+            1 => solution.part_one(input),
+            _ => solution.part_two(input),
+        },
+        Ok(Input::Stream(path, streaming)) => {
+            let mut input = BufReader::new(open_file(path)?);
+            match part {
+                1 => streaming.part_one(&mut input),
+                _ => streaming.part_two(&mut input),
+            }
+        }
+        Err(error) => Err(error.to_string().into()),
+    }
+}
+
+fn run_day(day_number: &u8, solution: &dyn Solution) -> ExitCode {
+    let path = PathBuf::from(input_file(day_number));
+    let input = load(&path, solution);
+    let results = [solve(&input, solution, 1), solve(&input, solution, 2)];
+    let exit_code = exit_code(&results);
+    for (part, result) in (1..).zip(results) {
+        println!("Day {day_number}, Part {part}: {}", show(result));
+    }
+    exit_code
+}
+
+/// Failure if any part ended in an error. Unsolved parts are not errors.
+fn exit_code<T, E>(results: &[Result<T, E>]) -> ExitCode {
+    if results.iter().all(Result::is_ok) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// One part's result as printed by `--json`: the answer as a number, a
+/// string or null for an unsolved part, or the error that stopped it.
+#[derive(Serialize)]
+struct PartResult {
+    day: u8,
+    part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn print_day_json(day_number: &u8, solution: &dyn Solution) -> ExitCode {
+    let path = PathBuf::from(input_file(day_number));
+    let input = load(&path, solution);
+    let mut exit_code = ExitCode::SUCCESS;
+    for part in [1, 2] {
+        let (answer, error) = match solve(&input, solution, part) {
+            Ok(answer) => (Some(answer), None),
+            Err(error) => {
+                exit_code = ExitCode::FAILURE;
+                (None, Some(error.to_string()))
+            }
+        };
+        let result = PartResult { day: *day_number, part, answer, error };
+        println!("{}", serde_json::to_string(&result).unwrap());
+    }
+    exit_code
+}
+
+fn show(result: Result<Answer, Box<dyn Error>>) -> String {
+    match result {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("error: {error}"),
+    }
+}
+
+/// Reading the input is counted too, since days that stream it never hold
+/// all of it, so each part loads it afresh.
+fn profile_day(day_number: &u8, solution: &dyn Solution) -> ExitCode {
+    let path = PathBuf::from(input_file(day_number));
+    let mut exit_code = ExitCode::SUCCESS;
+    for part in [1, 2] {
+        let (result, usage) = memory::measure(|| solve(&load(&path, solution), solution, part));
+        if result.is_err() {
+            exit_code = ExitCode::FAILURE;
+        }
+        println!("Day {day_number}, Part {part}: {} ({usage})", show(result));
+    }
+    exit_code
+}
+
+fn render_day_three(style: RenderStyle) -> ExitCode {
+    let Ok(input) = read_input(&3).inspect_err(|error| eprintln!("Day 3: {error}")) else {
+        return ExitCode::FAILURE;
+    };
+    let schematic = match Schematic::parse_new(&input) {
+        Ok(schematic) => schematic,
+        Err(error) => {
+            eprintln!("Day 3: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    print!("{}", day_three::render(&schematic, &GearRule::gear_ratio(), style));
+    ExitCode::SUCCESS
+}
+
+fn calibrate_day_one(vocabulary_file: &str) -> ExitCode {
+    let vocabulary = fs::read_to_string(vocabulary_file)
+        .map_err(|error| error.to_string())
+        .and_then(|contents| Vocabulary::parse_new(&normalise(&contents)).map_err(|error| error.to_string()));
+    let vocabulary = match vocabulary {
+        Ok(vocabulary) => vocabulary,
+        Err(error) => {
+            eprintln!("{vocabulary_file}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Ok(input) = read_input(&1).inspect_err(|error| eprintln!("Day 1: {error}")) else {
+        return ExitCode::FAILURE;
+    };
+    match day_one::calibrate(&input, &vocabulary) {
+        Ok(result) => println!("Day 1, {vocabulary_file}: {result}"),
+        Err(error) => {
+            eprintln!("Day 1: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn check_day_two_bag(config: &str) -> ExitCode {
+    let bag = match Bag::parse_new(config) {
+        Ok(bag) => bag,
+        Err(error) => {
+            eprintln!("Bag: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Ok(input) = read_input(&2).inspect_err(|error| eprintln!("Day 2: {error}")) else {
+        return ExitCode::FAILURE;
+    };
+    let games = match day_two::parse_games(&input, None) {
+        Ok(games) => games,
+        Err(error) => {
+            eprintln!("Day 2: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match day_two::sum_feasible_ids(&games, &bag) {
+        Ok(total) => println!("Day 2, Part 1: {total}"),
+        Err(error) => {
+            eprintln!("Day 2: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+    for game in &games {
+        for violation in bag.violations(game) {
+            println!("  {violation}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn analyse_day_two() -> ExitCode {
+    let Ok(input) = read_input(&2).inspect_err(|error| eprintln!("Day 2: {error}")) else {
+        return ExitCode::FAILURE;
+    };
+    match day_two::parse_games(&input, None) {
+        Ok(games) => print!("{}", day_two::analyse(&games)),
+        Err(error) => {
+            eprintln!("Day 2: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn trace_day_four<T: Tally>() -> ExitCode {
+    let Ok(input) = read_input(&4).inspect_err(|error| eprintln!("Day 4: {error}")) else {
+        return ExitCode::FAILURE;
+    };
+    let trace = day_four::parse_cards(&input)
+        .map_err(CardError::from)
+        .and_then(|cards| CascadeTrace::<T>::new(&cards));
+    match trace {
+        Ok(trace) => print!("{trace}"),
+        Err(error) => {
+            eprintln!("Day 4: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_day_four_wide() -> ExitCode {
+    let Ok(input) = read_input(&4).inspect_err(|error| eprintln!("Day 4: {error}")) else {
+        return ExitCode::FAILURE;
+    };
+    let cards = match day_four::parse_cards(&input) {
+        Ok(cards) => cards,
+        Err(error) => {
+            eprintln!("Day 4: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let results: [Result<u128, CardError>; 2] = [day_four::total_points(&cards), day_four::total_cards(&cards)];
+    let exit_code = exit_code(&results);
+    for (part, result) in (1..).zip(results) {
+        match result {
+            Ok(total) => println!("Day 4, Part {part}: {total}"),
+            Err(error) => println!("Day 4, Part {part}: error: {error}"),
+        }
+    }
+    exit_code
+}
+
+fn generate_input(args: &Args) -> ExitCode {
+    let mut generators: BTreeMap<u8, fn(usize, u64) -> String> = BTreeMap::new();
+    generators.insert(1, day_one::generate);
+    generators.insert(2, day_two::generate);
+    generators.insert(3, day_three::generate);
+    generators.insert(4, day_four::generate);
+    generators.insert(5, day_five::generate);
+    generators.insert(6, day_six::generate);
+    generators.insert(7, day_seven::generate);
+    generators.insert(8, day_eight::generate);
+
+    let Some(generator) = generators.get(&args.day_number) else {
+        eprintln!("generate needs a day between 1 and {}", generators.len());
+        return ExitCode::FAILURE;
+    };
+    let options = args.parse_value("--size", 100)
+        .and_then(|size| Ok((size, args.parse_value("--seed", 0)?)));
+    let (size, seed) = match options {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    print!("{}", generator(size, seed));
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,131 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::{self, Display},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static BYTES_IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES_IN_USE: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation and the most memory in
+/// use at once. A reallocation counts as one allocation of the bytes it
+/// grows by.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_growth(bytes: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(bytes, Ordering::Relaxed);
+        let in_use = BYTES_IN_USE.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        PEAK_BYTES_IN_USE.fetch_max(in_use, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            CountingAllocator::record_growth(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            CountingAllocator::record_growth(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        BYTES_IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            if new_size >= layout.size() {
+                CountingAllocator::record_growth(new_size - layout.size());
+            } else {
+                ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+                BYTES_IN_USE.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_pointer
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub allocations: usize,
+    pub bytes_allocated: usize,
+    /// The most memory in use at once beyond what was in use beforehand.
+    pub peak_bytes: usize,
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes_allocated),
+            format_bytes(self.peak_bytes),
+        )
+    }
+}
+
+/// Runs `f` and reports the allocations it made. The counts are global, so
+/// they only belong to `f` while nothing else is running.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, MemoryUsage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes_allocated = BYTES_ALLOCATED.load(Ordering::Relaxed);
+    let in_use = BYTES_IN_USE.load(Ordering::Relaxed);
+    PEAK_BYTES_IN_USE.store(in_use, Ordering::Relaxed);
+    let result = f();
+    let usage = MemoryUsage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed) - bytes_allocated,
+        peak_bytes: PEAK_BYTES_IN_USE.load(Ordering::Relaxed).saturating_sub(in_use),
+    };
+    (result, usage)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_counts_allocations_made_while_measuring() {
+        let (buffer, usage) = measure(|| vec![0u8; 4096]);
+        assert_eq!(buffer.len(), 4096);
+        assert!(usage.allocations >= 1);
+        assert!(usage.bytes_allocated >= 4096);
+    }
+
+    #[test]
+    fn it_formats_sizes_in_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    io::{self, Write as _},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use common::{panic_message, Answer, Solution};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const DAY_CRATES: [&str; 8] = ["day_one", "day_two", "day_three", "day_four", "day_five", "day_six", "day_seven", "day_eight"];
+
+/// One part solved on one of the watched files.
+#[derive(Debug, Clone, PartialEq)]
+struct PartRun {
+    file: String,
+    part: u8,
+    result: Result<Answer, String>,
+    time: Duration,
+}
+
+/// What is known about a watched file, which changes when it is edited.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>, Option<u64>)>;
+
+/// Solves both parts of a day on its input, and on its examples if
+/// `examples` is set, then again each time one of those files changes,
+/// until interrupted. The examples are any `exampleN.txt` or
+/// `exampleN_<name>.txt` in the working directory. The source of the day's
+/// crate and of `common` is watched too: when it changes the runner is
+/// rebuilt and restarted with the same arguments, or left running with the
+/// build errors on screen if it does not compile.
+pub fn watch(day_number: u8, solution: &dyn Solution, examples: bool) {
+    // Panics are caught and shown in place of the part's answer, so the
+    // default report would only be printed over the results.
+    panic::set_hook(Box::new(|_| {}));
+    let runner = env::current_exe();
+    let mut last_source = snapshot(&source_files(day_number));
+    let mut last_snapshot = Snapshot::new();
+    let mut previous = Vec::new();
+    loop {
+        let source = snapshot(&source_files(day_number));
+        if source != last_source {
+            match &runner {
+                Ok(runner) => rebuild_and_restart(runner),
+                Err(error) => println!("Cannot restart the runner: {error}"),
+            }
+            last_source = source;
+        }
+        let files = watched_files(day_number, examples);
+        let snapshot = snapshot(&files);
+        if snapshot != last_snapshot {
+            let runs = run_parts(&files, solution);
+            print!("{CLEAR_SCREEN}{}", render(day_number, &runs, &previous));
+            io::stdout().flush().ok();
+            previous = runs;
+            last_snapshot = snapshot;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn watched_files(day_number: u8, examples: bool) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from(crate::input_file(&day_number))];
+    if examples {
+        let mut examples: Vec<PathBuf> = fs::read_dir(".").into_iter().flatten().flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| is_example(name, day_number)))
+            .collect();
+        examples.sort();
+        files.extend(examples);
+    }
+    files
+}
+
+/// Every file under the `src` directories of the day's crate and `common`.
+fn source_files(day_number: u8) -> Vec<PathBuf> {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap_or(Path::new("."));
+    let mut files = Vec::new();
+    let day_crate = DAY_CRATES.get(usize::from(day_number).wrapping_sub(1));
+    for crate_name in day_crate.into_iter().chain(&["common"]) {
+        add_files(&workspace.join(crate_name).join("src"), &mut files);
+    }
+    files.sort();
+    files
+}
+
+fn add_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    for path in fs::read_dir(directory).into_iter().flatten().flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            add_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Builds the runner the way this one was built and, if that works, runs
+/// the new build in place of this process. Returns only if it could not.
+fn rebuild_and_restart(runner: &Path) {
+    println!("{CLEAR_SCREEN}Source changed, rebuilding...");
+    let mut build = Command::new(env::var_os("CARGO").unwrap_or("cargo".into()));
+    build.args(["build", "--quiet", "--bin", "main"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    match build.current_dir(env!("CARGO_MANIFEST_DIR")).status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            println!("Build failed, waiting for the source to change again");
+            return;
+        }
+        Err(error) => {
+            println!("Cannot run cargo: {error}");
+            return;
+        }
+    }
+    let error = restart(Command::new(runner).args(env::args_os().skip(1)));
+    println!("Cannot restart the runner: {error}");
+}
+
+#[cfg(unix)]
+fn restart(command: &mut Command) -> io::Error {
+    use std::os::unix::process::CommandExt;
+    command.exec()
+}
+
+#[cfg(not(unix))]
+fn restart(command: &mut Command) -> io::Error {
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => error,
+    }
+}
+
+fn is_example(file_name: &str, day_number: u8) -> bool {
+    let Some(stem) = file_name.strip_suffix(".txt") else {
+        return false;
+    };
+    let prefix = format!("example{day_number}");
+    stem == prefix || stem.strip_prefix(&prefix).is_some_and(|rest| rest.starts_with('_'))
+}
+
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files.iter()
+    .map(|path| {
+        let metadata = fs::metadata(path).ok();
+        let modified = metadata.as_ref().and_then(|metadata| metadata.modified().ok());
+        (path.clone(), modified, metadata.map(|metadata| metadata.len()))
+    })
+    .collect()
+}
+
+fn run_parts(files: &[PathBuf], solution: &dyn Solution) -> Vec<PartRun> {
+    let mut runs = Vec::new();
+    for path in files {
+        let input = crate::load(path, solution);
+        for part in [1, 2] {
+            let start = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| crate::solve(&input, solution, part)))
+                .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload.as_ref())).into()))
+                .map_err(|error| error.to_string());
+            let file = path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned());
+            runs.push(PartRun { file, part, result, time: start.elapsed() });
+        }
+    }
+    runs
+}
+
+/// Each file's answers and timings, with the answer from `previous` beside
+/// any that changed.
+fn render(day_number: u8, runs: &[PartRun], previous: &[PartRun]) -> String {
+    let mut output = format!("Day {day_number}, watching for changes to its input and source (Ctrl-C to stop)\n");
+    let mut file = None;
+    for run in runs {
+        if file != Some(&run.file) {
+            file = Some(&run.file);
+            let _ = write!(output, "\n{}\n", run.file);
+        }
+        let _ = write!(output, "  Part {}: {} ({:.2?})", run.part, show(&run.result), run.time);
+        let earlier = previous.iter().find(|earlier| earlier.file == run.file && earlier.part == run.part);
+        if let Some(earlier) = earlier.filter(|earlier| earlier.result != run.result) {
+            let _ = write!(output, "  was {}", show(&earlier.result));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn show(result: &Result<Answer, String>) -> String {
+    match result {
+        Ok(answer) => answer.to_string(),
+        Err(error) => error.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(file: &str, part: u8, answer: u64) -> PartRun {
+        PartRun { file: file.to_string(), part, result: Ok(Answer::Integer(answer)), time: Duration::from_millis(2) }
+    }
+
+    #[test]
+    fn it_shows_changed_answers_beside_the_previous_ones() {
+        let previous = [run("input7.txt", 1, 6440), run("input7.txt", 2, 5905)];
+        let runs = [run("input7.txt", 1, 6440), run("input7.txt", 2, 5906), run("example7.txt", 1, 1)];
+        let output = render(7, &runs, &previous);
+        assert_eq!(output, "Day 7, watching for changes to its input and source (Ctrl-C to stop)\n\
+            \n\
+            input7.txt\n  \
+            Part 1: 6440 (2.00ms)\n  \
+            Part 2: 5906 (2.00ms)  was 5905\n\
+            \n\
+            example7.txt\n  \
+            Part 1: 1 (2.00ms)\n");
+    }
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        fn part_one(&self, _: &str) -> Result<Answer, Box<dyn std::error::Error>> {
+            panic!("index out of bounds")
+        }
+
+        fn part_two(&self, _: &str) -> Result<Answer, Box<dyn std::error::Error>> {
+            Ok(Answer::Unsolved)
+        }
+    }
+
+    #[test]
+    fn it_shows_a_panic_as_the_parts_error() {
+        let runs = run_parts(&[PathBuf::from("Cargo.toml")], &Panicking);
+        let results: Vec<_> = runs.into_iter().map(|run| run.result).collect();
+        assert_eq!(results, vec![Err("panicked: index out of bounds".to_string()), Ok(Answer::Unsolved)]);
+    }
+
+    #[test]
+    fn it_watches_the_source_of_the_day_and_common() {
+        let files = source_files(7);
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert!(files.contains(&workspace.join("day_seven/src/lib.rs")));
+        assert!(files.contains(&workspace.join("common/src/lib.rs")));
+        assert!(!files.contains(&workspace.join("day_six/src/lib.rs")));
+    }
+
+    #[test]
+    fn it_only_watches_examples_when_asked() {
+        assert_eq!(watched_files(7, false), vec![PathBuf::from("input7.txt")]);
+    }
+
+    #[test]
+    fn it_recognises_a_days_example_files() {
+        assert!(is_example("example7.txt", 7));
+        assert!(is_example("example7_jokers.txt", 7));
+        assert!(!is_example("example17.txt", 7));
+        assert!(!is_example("example7.txt.bak", 7));
+        assert!(!is_example("input7.txt", 7));
+    }
+}
@@ -0,0 +1,160 @@
+use common::{find_disagreement, Answer, Solution};
+
+struct Day {
+    number: u8,
+    solution: &'static dyn Solution,
+    generate: fn(usize, u64) -> String,
+    /// A generated size that both parts can still answer.
+    size: usize,
+    references: Vec<Reference>,
+}
+
+/// A slow but obviously correct solver for one part, compared with the
+/// day's solution on inputs it can still answer in reasonable time.
+struct Reference {
+    part: u8,
+    solve: fn(&str) -> Answer,
+    generate: fn(usize, u64) -> String,
+    sizes: &'static [usize],
+}
+
+fn days() -> Vec<Day> {
+    vec![
+        Day {
+            number: 1,
+            solution: &day_one::Solution,
+            generate: day_one::generate,
+            size: 100,
+            references: vec![
+                Reference { part: 1, solve: |input| day_one::reference::part_one(input).into(), generate: day_one::generate, sizes: &[1, 10, 100] },
+                Reference { part: 2, solve: |input| day_one::reference::part_two(input).into(), generate: day_one::generate, sizes: &[1, 10, 100] },
+            ],
+        },
+        Day {
+            number: 2,
+            solution: &day_two::Solution,
+            generate: day_two::generate,
+            size: 100,
+            references: vec![
+                Reference { part: 1, solve: |input| day_two::reference::part_one(input).into(), generate: day_two::generate, sizes: &[1, 10, 100] },
+                Reference { part: 2, solve: |input| day_two::reference::part_two(input).into(), generate: day_two::generate, sizes: &[1, 10, 100] },
+            ],
+        },
+        Day {
+            number: 3,
+            solution: &day_three::Solution,
+            generate: day_three::generate,
+            size: 50,
+            references: vec![
+                Reference { part: 1, solve: |input| day_three::reference::part_one(input).into(), generate: day_three::generate, sizes: &[1, 10, 40] },
+                Reference { part: 2, solve: |input| day_three::reference::part_two(input).into(), generate: day_three::generate, sizes: &[1, 10, 40] },
+            ],
+        },
+        Day {
+            number: 4,
+            solution: &day_four::Solution,
+            generate: day_four::generate,
+            size: 1000,
+            references: vec![
+                Reference { part: 1, solve: |input| day_four::reference::part_one(input).into(), generate: day_four::generate, sizes: &[1, 10, 100] },
+                Reference { part: 2, solve: |input| day_four::reference::part_two(input).into(), generate: day_four::generate, sizes: &[1, 10, 100] },
+            ],
+        },
+        Day {
+            number: 5,
+            solution: &day_five::Solution,
+            generate: day_five::generate,
+            size: 50,
+            // The reference converts every seed, so the ranges are kept short.
+            references: vec![
+                Reference { part: 1, solve: |input| day_five::reference::part_one(input).into(), generate: |size, seed| day_five::generate_within(size, seed, 2000), sizes: &[1, 5, 20] },
+                Reference { part: 2, solve: |input| day_five::reference::part_two(input).into(), generate: |size, seed| day_five::generate_within(size, seed, 2000), sizes: &[1, 5, 20] },
+            ],
+        },
+        Day {
+            number: 6,
+            solution: &day_six::Solution,
+            generate: day_six::generate,
+            size: 4,
+            // Part two joins every race into one, which the reference then
+            // tries every hold time of.
+            references: vec![
+                Reference { part: 1, solve: |input| day_six::reference::part_one(input).into(), generate: day_six::generate, sizes: &[1, 4, 8] },
+                Reference { part: 2, solve: |input| day_six::reference::part_two(input).into(), generate: day_six::generate, sizes: &[1, 2] },
+            ],
+        },
+        Day {
+            number: 7,
+            solution: &day_seven::Solution,
+            generate: day_seven::generate,
+            size: 100,
+            references: vec![
+                Reference { part: 1, solve: |input| day_seven::reference::part_one(input).into(), generate: day_seven::generate, sizes: &[1, 10, 100] },
+                Reference { part: 2, solve: |input| day_seven::reference::part_two(input).into(), generate: day_seven::generate, sizes: &[1, 10, 100] },
+            ],
+        },
+        Day {
+            number: 8,
+            solution: &day_eight::Solution,
+            generate: day_eight::generate,
+            size: 500,
+            references: vec![
+                Reference { part: 1, solve: |input| day_eight::reference::part_one(input).into(), generate: day_eight::generate, sizes: &[2, 10, 100] },
+            ],
+        },
+    ]
+}
+
+fn solve(day: &Day, part: u8, input: &str) -> Result<Answer, String> {
+    let result = match part {
+        1 => day.solution.part_one(input),
+        _ => day.solution.part_two(input),
+    };
+    result.map_err(|error| format!("day {} part {part}: {error}", day.number))
+}
+
+#[test]
+fn it_generates_the_same_solvable_input_from_the_same_seed() {
+    for day in days() {
+        let input = (day.generate)(day.size, 3);
+        assert_eq!(input, (day.generate)(day.size, 3), "day {}", day.number);
+        assert_ne!(input, (day.generate)(day.size, 4), "day {}", day.number);
+        for part in [1, 2] {
+            if let Err(error) = solve(&day, part, &input) {
+                panic!("{error}");
+            }
+        }
+    }
+}
+
+#[test]
+fn it_agrees_with_the_reference_solvers() {
+    for day in days() {
+        for reference in &day.references {
+            let result = find_disagreement(
+                reference.generate,
+                |input| Some((reference.solve)(input)),
+                |input| solve(&day, reference.part, input).ok(),
+                reference.sizes,
+                0..20,
+            );
+            assert_eq!(result, None, "day {} part {}", day.number, reference.part);
+        }
+    }
+}
+
+#[test]
+fn it_never_panics_on_truncated_input() {
+    for day in days() {
+        let input = (day.generate)(3, 1);
+        for (end, _) in input.char_indices() {
+            for part in [1, 2] {
+                let _ = solve(&day, part, &input[..end]);
+            }
+            if let Some(streaming) = day.solution.streaming() {
+                let _ = streaming.part_one(&mut &input.as_bytes()[..end]);
+                let _ = streaming.part_two(&mut &input.as_bytes()[..end]);
+            }
+        }
+    }
+}